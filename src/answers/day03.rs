@@ -2,26 +2,29 @@ use std::collections::VecDeque;
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
+use crate::par;
+
+const DAY: i32 = 3;
+
 type Input = Vec<Vec<i32>>;
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day03")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -56,40 +59,38 @@ fn find_min_val<T: Ord + Copy>(vec: &VecDeque<T>) -> Option<usize> {
 
 fn solve(input: &Input, size: usize) -> usize {
     assert!(size > 1);
-    let mut result = 0;
+    par::sum_by(input, |bank| max_joltage(bank, size))
+}
 
-    for bank in input.iter() {
-        let mut max = VecDeque::with_capacity(size);
-        let mut iter = bank.iter().rev();
+fn max_joltage(bank: &[i32], size: usize) -> usize {
+    let mut max = VecDeque::with_capacity(size);
+    let mut iter = bank.iter().rev();
 
-        // save the first 'size' values
-        for &val in iter.by_ref().take(size) {
-            max.push_front(val);
-        }
+    // save the first 'size' values
+    for &val in iter.by_ref().take(size) {
+        max.push_front(val);
+    }
 
-        for &val in iter {
-            let leftmost = *max.front().expect("VecDeque should not be empty");
-
-            if val >= leftmost {
-                // remove worst value, prepend new value
-                if let Some(idx) = find_min_val(&max) {
-                    max.remove(idx);
-                    max.push_front(val);
-                } else {
-                    // all values are 9
-                    break;
-                }
+    for &val in iter {
+        let leftmost = *max.front().expect("VecDeque should not be empty");
+
+        if val >= leftmost {
+            // remove worst value, prepend new value
+            if let Some(idx) = find_min_val(&max) {
+                max.remove(idx);
+                max.push_front(val);
+            } else {
+                // all values are 9
+                break;
             }
         }
-
-        let mut res = 0;
-        for val in max {
-            res = res*10 + val as usize;
-        }
-        result += res;
     }
 
-    result
+    let mut res = 0;
+    for val in max {
+        res = res*10 + val as usize;
+    }
+    res
 }
 
 #[cfg(test)]
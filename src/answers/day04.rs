@@ -1,28 +1,29 @@
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
-use crate::matrix::{Matrix, Pos};
+use crate::config::Config;
+use crate::matrix::{Connectivity, Matrix, Pos};
+
+const DAY: i32 = 4;
 
 type Input = Matrix<char>;
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day04")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
-    let mut input = parse(contents);
+    let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&mut input.clone());
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
-    let part2_res = part2(&mut input);
+    let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -32,60 +33,22 @@ fn parse(contents: String) -> Input {
 }
 
 fn part1(input: &mut Input) -> usize {
-    let rows = input.rows();
-    let cols = input.cols();
-    let mut adjacency = Matrix::<i8>::with_default(rows, cols, -1);
-
-    let possible_adj = [Pos(0,1), Pos(1,-1), Pos(1,0), Pos(1,1)];
-    let mut remove_count = 0;
-
-    for pos in Pos::iter(rows, cols) {
-        // println!("pos: {pos}, val: {val}");
-        if input[pos] != '@' { continue; }
-
-        adjacency[pos] += 1;
-
-        for p_adj in possible_adj {
-            let next = pos + p_adj;
-
-            if let Some('@') = input.get(next) {
-                adjacency[pos] += 1;
-                adjacency[next] += 1;
-
-                // assert!(adjacency[pos] < 10);
-                // assert!(adjacency[next] < 10);
-            }
+    let removable: Vec<Pos> = input.iter_pos()
+        .filter(|(pos, val)| **val == '@' && input.count_neighbors8(*pos, |c| *c == '@') < 4)
+        .map(|(pos, _)| pos)
+        .collect();
 
-        }
-
-        if (0..4).contains(&adjacency[pos]) {
-            input[pos] = '.';
-            remove_count += 1;
-        }
+    for pos in &removable {
+        input[*pos] = '.';
     }
 
-    // println!("adjacency:\n{adjacency}");
-    // for (pos, val) in adjacency.iter_pos() {
-    //     if (0..4).contains(val) {
-    //         input[pos] = '.';
-    //         remove_count += 1;
-    //     }
-    // }
-
-    remove_count
+    removable.len()
 }
 
-fn part2(input: &mut Input) -> usize {
-    let mut res = 0;
-
-    loop {
-        let remove_count = part1(input);
-        if remove_count == 0 { break; }
+fn part2(input: &Input) -> usize {
+    let (removed, _) = input.peel(Connectivity::Eight, 4, |c| *c == '@');
 
-        res += remove_count;
-    }
-
-    res
+    removed
 }
 
 #[cfg(test)]
@@ -127,9 +90,9 @@ mod tests {
 .@@@@@@@@.
 @.@.@@@.@.";
 
-        let mut input = parse(contents.to_string());
+        let input = parse(contents.to_string());
 
-        let res = part2(&mut input);
+        let res = part2(&input);
 
         assert_eq!(res, 43);
     }
@@ -1,29 +1,30 @@
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
 use crate::insert_list::InsertList;
 use crate::bintree::RangeTree;
 
+const DAY: i32 = 5;
+
 type Input = (RangeTree, Vec<i64>);
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day05")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -1,6 +1,9 @@
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
+use crate::matrix::Matrix;
+
 #[derive(PartialEq, Eq, Debug)]
 enum Ops {
     Add,
@@ -16,34 +19,36 @@ impl Ops {
     }
 }
 
-type Input = (Vec<Vec<char>>, Vec<Ops>);
+const DAY: i32 = 6;
+
+type Input = (Matrix<char>, Vec<Ops>);
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day06")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
 
 fn parse(contents: String) -> Input {
-    let lines: Vec<Vec<char>> = contents.lines().map(|str| str.chars().collect()).collect();
+    let (grid, ops) = contents.trim_end_matches(['\n', '\r'])
+        .rsplit_once('\n')
+        .expect("Input should have numbers and ops");
     
-    let ops = lines.last().unwrap().iter().collect::<String>()
+    let ops = ops
         .split_whitespace()
         .map(|op| {
             match op {
@@ -54,14 +59,14 @@ fn parse(contents: String) -> Input {
         })
         .collect();
 
-    (lines[0..lines.len()-1].to_vec(), ops)
+    (grid.try_into().expect("Input should be a valid matrix"), ops)
 }
 
 fn part1(input: &Input) -> i64 {
     let (lines, ops) = input;
 
     let mut rows: Vec<Vec<String>> = Vec::with_capacity(4);
-    for line in &lines[0..lines.len()] {
+    for line in lines.rows_iter() {
         let row = line.split(|c| *c == ' ')
             .filter(|substr| !substr.is_empty())
             .map(|str| str.iter().collect::<String>())
@@ -86,26 +91,16 @@ fn part1(input: &Input) -> i64 {
 
 fn part2(input: &Input) -> i64 {
     let (lines, ops) = input;
-    let rows = lines.len();
 
     let mut res = 0;
     let mut problem = 0;
     let problems = ops.len();
     let mut inner_res = if ops[problem] == Ops::Add { 0 } else { 1 };
 
-    let mut val_col = 0;
-    let columns = lines.first().unwrap().len();
-    let mut row = 0;
-
     // Move vertically on the input.
     // If we find a column full of ' ' then we go to next problem
-    while val_col < columns {
-        let mut value = String::new();
-        while row < rows && lines[row][val_col] == ' ' { row+=1; }
-        while row < rows && lines[row][val_col] != ' ' { 
-            value.push(lines[row][val_col]);
-            row += 1;
-        }
+    for column in lines.cols_iter() {
+        let value: String = column.filter(|c| **c != ' ').collect();
 
         if value.is_empty() {
             // empty row, next problem
@@ -115,11 +110,8 @@ fn part2(input: &Input) -> i64 {
                 inner_res = if ops[problem] == Ops::Add { 0 } else { 1 };
             }
         } else {
-            ops[problem].apply(&mut inner_res, value.trim().parse().unwrap());
+            ops[problem].apply(&mut inner_res, value.parse().unwrap());
         }
-        
-        row = 0;
-        val_col += 1;
     }
 
     res += inner_res;
@@ -2,40 +2,42 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
 use crate::matrix::{Matrix, Pos};
 
+const DAY: i32 = 7;
+
 type Input = (Matrix<char>, Pos);
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day07")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
 
 fn parse(contents: String) -> Input {
-    let start = contents.find("S").unwrap() as i32;
-    (contents.try_into().expect("Input should be a valid matrix"), Pos(0, start))
+    let mat: Matrix<char> = contents.try_into().expect("Input should be a valid matrix");
+    let start = mat.position(|c| *c == 'S').expect("Input should have a start");
+    (mat, start)
 }
 
 fn part1(input: &Input) -> i64 {
     let (mat, start) = input;
-    let mut splits = 0;
+    let mut splitters = HashSet::new();
 
     let mut visited = HashSet::new();
     let mut beams = VecDeque::new();
@@ -43,28 +45,19 @@ fn part1(input: &Input) -> i64 {
     beams.push_back(*start);
 
     while let Some(beam) = beams.pop_front() {
-        // println!("Curr beam: {beam}");
         // if already visited, continue. Else, mark as visited
         if !visited.insert(beam) { continue; }
 
-        let down_pos = beam + Pos(1, 0);
-        match mat.get(down_pos) {
-            Some('^') => {
-                // println!("Found splitter");
-                splits += 1;
-                beams.push_back(down_pos + Pos(0, -1));
-                beams.push_back(down_pos + Pos(0, 1));
-            },
-            Some('.') => {
-                // println!("Going down!");
-                beams.push_back(down_pos);
-            },
-            // Some(c) => { println!("Unexpected char: {c}"); }
-            _ => {} // { println!("Didn't match anything!"); }
-        } 
+        // beams from different cells can fall onto the same splitter
+        if let Some((splitter, _)) = mat.first_hit(beam, Pos(1, 0), |c| *c == '^')
+            && splitters.insert(splitter)
+        {
+            beams.push_back(splitter + Pos(0, -1));
+            beams.push_back(splitter + Pos(0, 1));
+        }
     }
 
-    splits
+    splitters.len() as i64
 }
 
 fn part2(input: &Input) -> i64 {
@@ -77,27 +70,18 @@ fn multiple_worlds(
     cache: &mut HashMap<Pos, i64>,
     beam: Pos,
 ) -> i64 {
-    // println!("Curr beam: {beam}");
-    // if already visited, continue. Else, return cache
+    // if already visited, return cache
     if let Some(res) = cache.get(&beam) {
         return *res;
     }
 
-    let down_pos = beam + Pos(1, 0);
-    let mut timelines;
-    match mat.get(down_pos) {
-        Some('^') => {
-            // println!("Found splitter");
-            timelines = multiple_worlds(mat, cache, down_pos + Pos(0,-1));
-            timelines += multiple_worlds(mat, cache, down_pos + Pos(0,1));
-        },
-        Some('.') => {
-            // println!("Going down!");
-            timelines = multiple_worlds(mat, cache, down_pos);
+    let timelines = match mat.first_hit(beam, Pos(1, 0), |c| *c == '^') {
+        Some((splitter, _)) => {
+            multiple_worlds(mat, cache, splitter + Pos(0, -1))
+                + multiple_worlds(mat, cache, splitter + Pos(0, 1))
         },
-        // Some(c) => { println!("Unexpected char: {c}"); }
-        _ => { timelines = 1 } // { println!("Didn't match anything!"); }
-    } 
+        None => 1,
+    };
 
     cache.insert(beam, timelines);
     timelines
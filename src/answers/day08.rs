@@ -3,18 +3,19 @@ use std::collections::{BinaryHeap};
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
+use crate::matrix::Pos3;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Node {
     pub id: usize,
-    pub x: i64,
-    pub y: i64,
-    pub z: i64,
+    pub pos: Pos3,
     pub component: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Edge {
-    pub dis: i64,
+    pub dis: u128,
     pub id1: usize,
     pub id2: usize,
 }
@@ -31,32 +32,26 @@ impl PartialOrd for Edge {
     }
 }
 
-impl Node {
-    pub fn euclidean_distance(&self, other: &Node) -> i64 {
-        (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)
-    }
-}
+const DAY: i32 = 8;
 
 type Input = Vec<Node>;
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day08")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(input.clone());
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -69,7 +64,7 @@ fn parse(contents: String) -> Input {
             let y = tup.next().unwrap().parse().unwrap();
             let z = tup.next().unwrap().parse().unwrap();
 
-            Node { id, x, y, z, component: None }
+            Node { id, pos: Pos3(x, y, z), component: None }
         })
         .collect()
 }
@@ -115,7 +110,7 @@ fn part2(mut input: Input) -> i64 {
         merge_comp(&mut input, id1, id2, &mut components);
 
         if components[0] == input.len() {
-            return input[id1].x * input[id2].x;
+            return input[id1].pos.0 as i64 * input[id2].pos.0 as i64;
         }
     }
 
@@ -129,7 +124,7 @@ fn create_edges(input: &Input) -> BinaryHeap<Reverse<Edge>> {
         let mut other_id = node.id + 1;
         while other_id < input.len() {
             let other = &input[other_id];
-            edges.push(Reverse(Edge { id1: node.id, id2: other_id, dis: node.euclidean_distance(other) }));
+            edges.push(Reverse(Edge { id1: node.id, id2: other_id, dis: node.pos.squared_euclidean_distance(&other.pos).expect("Distance should fit in u128") }));
 
             other_id += 1;
         }
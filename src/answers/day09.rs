@@ -2,28 +2,29 @@ use std::collections::{BinaryHeap, HashMap};
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
-use crate::matrix::{Pos};
+use crate::config::Config;
+use crate::matrix::Pos;
 
-type Input = Vec<Pos>;
+const DAY: i32 = 9;
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day09")?; // inputs/dayxx
+type Input = Vec<Pos<i64>>;
+
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -59,11 +60,10 @@ fn part1(input: &Input) -> i64 {
     max.dis
 }
 
-fn area(p1: &Pos, p2: &Pos) -> i64 {
-    let Pos(a,b) = p1;
-    let Pos(x,y) = p2;
+fn area(p1: &Pos<i64>, p2: &Pos<i64>) -> i64 {
+    let Pos(width, height) = p1.abs_diff(p2);
 
-    (1 + b.abs_diff(*y) as i64) * (1 + a.abs_diff(*x) as i64)
+    ((1 + width) * (1 + height)) as i64
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -134,20 +134,17 @@ fn part2(input: &Input) -> i64 {
     max_area
 }
 
-fn get_rect_area(from: Pos, other: Pos) -> i64 {
-    let width = 1 + from.0.abs_diff(other.0) as i64;
-    let height = 1 + from.1.abs_diff(other.1) as i64;
-
-    width * height
+fn get_rect_area(from: Pos<i64>, other: Pos<i64>) -> i64 {
+    area(&from, &other)
 }
 
 struct Region {
-    min: Pos,
-    max: Pos,
+    min: Pos<i64>,
+    max: Pos<i64>,
 }
 
 impl Region {
-    pub fn from_two_points(p1: &Pos, p2: &Pos) -> Region {
+    pub fn from_two_points(p1: &Pos<i64>, p2: &Pos<i64>) -> Region {
         let minx = p1.0.min(p2.0);
         let miny = p1.1.min(p2.1);
         let maxx = p1.0.max(p2.0);
@@ -155,11 +152,11 @@ impl Region {
         Region { min: Pos(minx, miny), max: Pos(maxx, maxy) }
     }
 
-    pub fn encloses(&self, point: Pos) -> bool {
+    pub fn encloses(&self, point: Pos<i64>) -> bool {
         point.0 > self.min.0 && point.0 < self.max.0 && point.1 > self.min.1 && point.1 < self.max.1
     }
 
-    pub fn encloses_line_midpoint(&self, start: Pos, end: Pos) -> bool {
+    pub fn encloses_line_midpoint(&self, start: Pos<i64>, end: Pos<i64>) -> bool {
         let mid = Pos((start.0 + end.0) / 2, (start.1 + end.1) / 2);
 
         self.encloses(mid)
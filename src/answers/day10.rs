@@ -1,9 +1,13 @@
-use std::collections::VecDeque;
 use std::{fs::read_to_string, io};
 use std::time::Instant;
 
+use crate::config::Config;
+use crate::{par, search};
+
 use microlp::{LinearExpr, Problem};
 
+const DAY: i32 = 10;
+
 type Input = Vec<Machine>;
 
 struct Machine {
@@ -14,11 +18,6 @@ struct Machine {
 
 impl Machine {
     pub fn configure_steps(&self) -> i64 {
-        // println!("Configuring steps!!\n");
-        let mut queue = VecDeque::new();
-
-        // println!("butons: {:#?}", self.buttons);
-
         let mut buttons = Vec::new();
         for vals in &self.buttons {
             let mut button = 0;
@@ -30,22 +29,14 @@ impl Machine {
             buttons.push(button);
         }
 
-        queue.extend(buttons.iter().map(|button| (0, button, 0)));
-        
-        while let Some((curr, button, steps)) = queue.pop_front() {
-            // println!("goal: {:#032b}", self.target);
-            // println!("curr: {curr:#032b}, button: {button:#b}, steps: {steps}");
-            if curr == self.target {
-                // println!("FOUND TARGET IN {steps} STEPS!!!");
-                return steps;
-            }
-
-            let new_curr = curr ^ *button;
-            // println!("new : {new_curr:#032b}");
-            queue.extend(buttons.iter().map(|button| (new_curr, button, steps+1)));
-        }
+        // Every light state is a node, pressing a button toggles its lights
+        let search = search::bfs(
+            [0],
+            |curr| buttons.iter().map(|button| curr ^ button).collect::<Vec<i64>>(),
+            |curr| *curr == self.target,
+        );
 
-        -1
+        search.goal_cost().map_or(-1, |steps| steps as i64)
     }
 
     pub fn joltage_steps(&self) -> i64 {
@@ -76,24 +67,22 @@ impl Machine {
     }
 }
 
-pub fn answer() -> Result<(), io::Error>{
-    let contents = read_to_string("inputs/day10")?; // inputs/dayxx
+pub fn answer(config: &Config) -> Result<(), io::Error>{
+    let contents = read_to_string(config.input_path(DAY))?;
     
     let input = parse(contents);
     
-    println!("Part1:");
+    config.print_part(1);
     let now = Instant::now();
     let part1_res = part1(&input);
     let elapsed = now.elapsed();
-    println!("result = {part1_res}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 1, part1_res, elapsed);
 
-    println!("Part2:");
+    config.print_part(2);
     let now = Instant::now();
     let part2_res = part2(&input);
     let elapsed = now.elapsed();
-    println!("result = {part2_res:?}");
-    println!("Time taken: {:.2?}", elapsed);
+    config.print_result(DAY, 2, part2_res, elapsed);
 
     Ok(())
 }
@@ -158,23 +147,11 @@ fn parse(contents: String) -> Input {
 }
 
 fn part1(input: &Input) -> i64 {
-    let mut res = 0;
-
-    for machine in input {
-        res += machine.configure_steps();
-    }
-
-    res
+    par::sum_by(input, Machine::configure_steps)
 }
 
 fn part2(input: &Input) -> i64 {
-    let mut res = 0;
-
-    for machine in input {
-        res += machine.joltage_steps();
-    }
-
-    res
+    par::sum_by(input, Machine::joltage_steps)
 }
 
 #[cfg(test)]
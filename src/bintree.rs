@@ -36,6 +36,36 @@ impl<T> BinTree<T> {
 
         Self { val: val.remove(0), left, right }
     }
+
+    #[cfg(feature = "serde")]
+    fn in_order<'a>(&'a self, out: &mut Vec<&'a T>) {
+        if let Some(left) = &self.left { left.in_order(out); }
+        out.push(&self.val);
+        if let Some(right) = &self.right { right.in_order(out); }
+    }
+}
+
+/// The ranges in order, the same shape the tree deserializes from.
+#[cfg(feature = "serde")]
+impl serde::Serialize for RangeTree {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut vals = Vec::new();
+        self.in_order(&mut vals);
+        serializer.collect_seq(vals)
+    }
+}
+
+/// Rebuilt through [`BinTree::new`], so the search order always holds.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RangeTree {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let list = InsertList::deserialize(deserializer)?;
+        if list.is_empty() {
+            return Err(serde::de::Error::invalid_length(0, &"at least one range"));
+        }
+
+        Ok(Self::new(list))
+    }
 }
 
 pub type RangeTree = BinTree<RangeInclusive<i64>>;
@@ -77,3 +107,33 @@ fn check_fresh(opt: &Option<Box<RangeTree>>, id: i64) -> bool {
         Some(tt) => tt.check_fresh(id),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_tree() {
+        let tree = RangeTree::new(InsertList::new(vec![3..=5, 10..=14, 16..=20, 12..=18]));
+
+        assert_eq!(tree.fresh_count(), 14);
+        assert!(tree.check_fresh(17) && !tree.check_fresh(8));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let tree = RangeTree::new(InsertList::new(vec![3..=5, 10..=14, 16..=20, 12..=18]));
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(json, r#"[{"start":3,"end":5},{"start":10,"end":20}]"#);
+
+        let back: RangeTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{back:?}"), format!("{tree:?}"));
+
+        // Rebuilt from unsorted, overlapping ranges instead of trusted
+        let messy: RangeTree = serde_json::from_str(r#"[{"start":16,"end":20},{"start":3,"end":5},{"start":10,"end":17}]"#).unwrap();
+        assert_eq!(serde_json::to_string(&messy).unwrap(), json);
+        assert!(messy.check_fresh(4) && messy.check_fresh(18) && !messy.check_fresh(8));
+        assert!(serde_json::from_str::<RangeTree>("[]").is_err());
+    }
+}
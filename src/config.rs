@@ -0,0 +1,333 @@
+use std::{env, error::Error as ErrorTrait, fmt::{self, Debug, Display}, fs, io, path::{Path, PathBuf}, str::FromStr, time::Duration};
+
+pub const CONFIG_FILE: &str = ".aoc.toml";
+
+/// Line of the day template replaced by the new day's number.
+pub const DAY_PLACEHOLDER: &str = "const DAY: i32 = 0;";
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Syntax { line: usize, msg: String },
+    UnknownKey { line: usize, key: String },
+    InvalidValue { key: String, value: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "Couldn't read {CONFIG_FILE}: {err}"),
+            Error::Syntax { line, msg } => write!(f, "{CONFIG_FILE}:{line}: {msg}"),
+            Error::UnknownKey { line, key } => write!(f, "{CONFIG_FILE}:{line}: unknown key '{key}'"),
+            Error::InvalidValue { key, value } => write!(f, "Invalid value '{value}' for '{key}'"),
+        }
+    }
+}
+
+impl ErrorTrait for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Headers, results and timings for every part.
+    #[default]
+    Text,
+    /// One `dayXX partN: result (time)` line per part.
+    Compact,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "compact" => Ok(Format::Compact),
+            _ => Err(Error::InvalidValue { key: "format".to_string(), value: s.to_string() }),
+        }
+    }
+}
+
+/// Runner settings. Built from the defaults, then `.aoc.toml`, then
+/// `AOC_*` environment variables, then command line flags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub year: u32,
+    pub inputs_dir: PathBuf,
+    pub answers_dir: PathBuf,
+    pub template: PathBuf,
+    pub format: Format,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: 2025,
+            inputs_dir: PathBuf::from("inputs"),
+            answers_dir: PathBuf::from("src/answers"),
+            template: PathBuf::from("src/answers/base.rs"),
+            format: Format::Text,
+        }
+    }
+}
+
+impl Config {
+    /// Looks for `.aoc.toml` in the current directory and its ancestors,
+    /// then applies the environment overrides.
+    pub fn load() -> Result<Self, Error> {
+        Self::load_from(&env::current_dir()?, |var| env::var(var).ok())
+    }
+
+    /// [`Config::load`] starting the search at `dir`, reading the overrides
+    /// through `var`.
+    fn load_from(dir: &Path, var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let mut config = match find_config(dir) {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.apply_vars(var)?;
+
+        Ok(config)
+    }
+
+    /// Relative paths in the file are resolved against the file's directory.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)?;
+        let mut config = Self::default();
+        config.apply_toml(&contents)?;
+
+        if let Some(root) = path.parent() {
+            for dir in [&mut config.inputs_dir, &mut config.answers_dir, &mut config.template] {
+                if dir.is_relative() {
+                    *dir = root.join(&*dir);
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn apply_toml(&mut self, contents: &str) -> Result<(), Error> {
+        for (idx, line) in contents.lines().enumerate() {
+            let line_num = idx + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() || line.starts_with('[') { continue; }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(Error::Syntax { line: line_num, msg: "expected 'key = value'".to_string() });
+            };
+            let key = key.trim();
+            let value = parse_value(value.trim())
+                .ok_or_else(|| Error::Syntax { line: line_num, msg: format!("couldn't parse value for '{key}'") })?;
+
+            match key {
+                "year" | "inputs_dir" | "answers_dir" | "template" | "format" => self.set(key, value)?,
+                _ => return Err(Error::UnknownKey { line: line_num, key: key.to_string() }),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_env(&mut self) -> Result<(), Error> {
+        self.apply_vars(|var| env::var(var).ok())
+    }
+
+    fn apply_vars(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), Error> {
+        let vars = [
+            ("AOC_YEAR", "year"),
+            ("AOC_INPUTS_DIR", "inputs_dir"),
+            ("AOC_ANSWERS_DIR", "answers_dir"),
+            ("AOC_TEMPLATE", "template"),
+            ("AOC_FORMAT", "format"),
+        ];
+        for (name, key) in vars {
+            if let Some(value) = var(name) {
+                self.set(key, &value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets a single setting by its `.aoc.toml` key name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let invalid = || Error::InvalidValue { key: key.to_string(), value: value.to_string() };
+        match key {
+            "year" => self.year = value.parse().map_err(|_| invalid())?,
+            "inputs_dir" => self.inputs_dir = PathBuf::from(value),
+            "answers_dir" => self.answers_dir = PathBuf::from(value),
+            "template" => self.template = PathBuf::from(value),
+            "format" => self.format = value.parse()?,
+            _ => return Err(invalid()),
+        }
+
+        Ok(())
+    }
+
+    pub fn day_name(day: i32) -> String {
+        format!("day{day:02}")
+    }
+
+    pub fn input_path(&self, day: i32) -> PathBuf {
+        self.inputs_dir.join(Self::day_name(day))
+    }
+
+    pub fn answer_path(&self, day: i32) -> PathBuf {
+        self.answers_dir.join(Self::day_name(day) + ".rs")
+    }
+
+    /// The file holding the `pub mod dayXX;` declarations.
+    pub fn answers_mod_path(&self) -> PathBuf {
+        self.answers_dir.with_extension("rs")
+    }
+
+    /// The template with its `const DAY: i32 = 0;` line set to `day`, or
+    /// `None` if the template has no such line.
+    pub fn fill_template(template: &str, day: i32) -> Option<String> {
+        template.contains(DAY_PLACEHOLDER)
+            .then(|| template.replacen(DAY_PLACEHOLDER, &format!("const DAY: i32 = {day};"), 1))
+    }
+
+    /// Heading printed before a part runs, only in the text format.
+    pub fn print_part(&self, part: u8) {
+        if self.format == Format::Text {
+            println!("Part{part}:");
+        }
+    }
+
+    pub fn print_result(&self, day: i32, part: u8, result: impl Display + Debug, elapsed: Duration) {
+        match self.format {
+            Format::Text => {
+                // Part 2 results are debug-printed, as in the template
+                if part == 2 {
+                    println!("result = {result:?}");
+                } else {
+                    println!("result = {result}");
+                }
+                println!("Time taken: {:.2?}", elapsed);
+            },
+            Format::Compact => println!("{} part{part}: {result} ({:.2?})", Self::day_name(day), elapsed),
+        }
+    }
+}
+
+fn find_config(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {},
+        }
+    }
+
+    line
+}
+
+/// Accepts a basic `"string"` or a bare word/number.
+fn parse_value(value: &str) -> Option<&str> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        quoted.strip_suffix('"').filter(|inner| !inner.contains('"'))
+    } else if !value.is_empty() && !value.contains(char::is_whitespace) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_toml() {
+        let contents = "\
+# runner defaults
+year = 2024
+inputs_dir = \"puzzles/inputs\" # trailing comment
+format = \"compact\"
+";
+
+        let mut config = Config::default();
+        config.apply_toml(contents).unwrap();
+
+        assert_eq!(config.year, 2024);
+        assert_eq!(config.inputs_dir, PathBuf::from("puzzles/inputs"));
+        assert_eq!(config.format, Format::Compact);
+        assert_eq!(config.input_path(7), PathBuf::from("puzzles/inputs/day07"));
+        assert_eq!(config.answers_mod_path(), PathBuf::from("src/answers.rs"));
+    }
+
+    #[test]
+    fn test_apply_toml_errors() {
+        let mut config = Config::default();
+
+        assert!(matches!(config.apply_toml("yaer = 2024"), Err(Error::UnknownKey { line: 1, .. })));
+        assert!(matches!(config.apply_toml("\nyear"), Err(Error::Syntax { line: 2, .. })));
+        assert!(matches!(config.apply_toml("format = \"xml\""), Err(Error::InvalidValue { .. })));
+    }
+
+    /// Empty scratch directory unique to `name`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-config-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_from_file_resolves_paths() {
+        let dir = scratch_dir("from-file");
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "inputs_dir = \"inputs\"\nanswers_dir = \"/abs/answers\"\n").unwrap();
+
+        let config = Config::from_file(&path).unwrap();
+
+        assert_eq!(config.inputs_dir, dir.join("inputs"));
+        assert_eq!(config.answers_dir, PathBuf::from("/abs/answers"));
+        assert_eq!(config.template, dir.join("src/answers/base.rs"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_walks_ancestors_and_env_wins() {
+        let dir = scratch_dir("load");
+        let nested = dir.join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join(CONFIG_FILE), "year = 2023\nformat = \"compact\"\n").unwrap();
+
+        let config = Config::load_from(&nested, |_| None).unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.format, Format::Compact);
+        assert_eq!(config.inputs_dir, dir.join("inputs"));
+
+        let config = Config::load_from(&nested, |var| (var == "AOC_YEAR").then(|| "2022".to_string())).unwrap();
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.format, Format::Compact);
+
+        assert!(Config::load_from(&nested, |var| (var == "AOC_FORMAT").then(|| "xml".to_string())).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let template = "const DAY: i32 = 0;\nconst DAYS: i32 = 25; // MONDAY\nfn f() -> i32 { DAY }\n";
+
+        let filled = Config::fill_template(template, 7).unwrap();
+
+        assert_eq!(filled, "const DAY: i32 = 7;\nconst DAYS: i32 = 25; // MONDAY\nfn f() -> i32 { DAY }\n");
+        assert_eq!(Config::fill_template("fn main() {}", 7), None);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{cmp::Ordering, ops::{Index, IndexMut, RangeInclusive}};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InsertList<T>(Vec<T>);
 
 impl<T> InsertList<T> {
@@ -86,6 +87,15 @@ impl InsertList<RangeInclusive<i64>> {
     }
 }
 
+/// Goes through [`InsertList::new`], so stored ranges come back sorted and
+/// merged. Other lists have no known order to restore.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for InsertList<RangeInclusive<i64>> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::new)
+    }
+}
+
 impl<T> Index<usize> for InsertList<T> {
     type Output = T;
 
@@ -99,3 +109,30 @@ impl<T> IndexMut<usize> for InsertList<T> {
         &mut self.0[index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_sorts_and_merges() {
+        let ranges = InsertList::new(vec![16..=20, 3..=5, 10..=14, 12..=18]);
+
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0], 3..=5);
+        assert_eq!(ranges[1], 10..=20);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_normalizes() {
+        let ranges = InsertList::new(vec![3..=5, 10..=14, 16..=20, 12..=18]);
+        let json = serde_json::to_string(&ranges).unwrap();
+        assert_eq!(json, r#"[{"start":3,"end":5},{"start":10,"end":20}]"#);
+
+        // Unsorted, overlapping input is normalized instead of trusted
+        let messy = r#"[{"start":16,"end":20},{"start":3,"end":5},{"start":10,"end":17}]"#;
+        let back: InsertList<_> = serde_json::from_str(messy).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+}
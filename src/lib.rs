@@ -1,4 +1,7 @@
 pub mod answers;
+pub mod config;
 pub mod matrix;
 pub mod insert_list;
 pub mod bintree;
+pub mod search;
+pub mod par;
@@ -1,17 +1,49 @@
-use std::{error::Error, fs, io::{self, Write}, path::Path, process::exit, time::Instant};
-use advent_of_code_2025::answers;
+use std::{error::Error, fs, io::{self, Write}, process::exit, time::Instant};
+use advent_of_code_2025::{answers, config::{Config, DAY_PLACEHOLDER}};
+
+const USAGE: &str = "\
+Usage: advent-of-code-2025 [OPTIONS]
+  -d, --day <DAY>          Run one day, 0 for all
+  -m, --make <DAY>         Create a new day from the template
+  -y, --year <YEAR>        Year shown in the header
+  -i, --inputs <DIR>       Directory with the puzzle inputs
+  -a, --answers <DIR>      Directory with the day modules
+  -t, --template <FILE>    Template for new days
+  -f, --format <FORMAT>    Output format, text or compact";
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = std::env::args().collect();
-
-    let num: i32 = if args.len() == 3 {
-        if args[1] == "-d" || args[1] == "--day" {
-            args[2].parse::<i32>()?
-        } else if args[1] == "-m" || args[1] == "--make" {
-            return add_new_day(args[2].parse()?);
-        } else {
+    let mut config = Config::load()?;
+
+    let mut args = std::env::args().skip(1);
+    let mut day = None;
+    let mut make = None;
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            eprintln!("Error: Missing value for {arg}\n{USAGE}");
             exit(1);
+        };
+
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(value.parse::<i32>()?),
+            "-m" | "--make" => make = Some(value.parse::<i32>()?),
+            "-y" | "--year" => config.set("year", &value)?,
+            "-i" | "--inputs" => config.set("inputs_dir", &value)?,
+            "-a" | "--answers" => config.set("answers_dir", &value)?,
+            "-t" | "--template" => config.set("template", &value)?,
+            "-f" | "--format" => config.set("format", &value)?,
+            _ => {
+                eprintln!("Error: Unknown option {arg}\n{USAGE}");
+                exit(1);
+            },
         }
+    }
+
+    if let Some(day) = make {
+        return add_new_day(&config, day);
+    }
+
+    let num: i32 = if let Some(day) = day {
+        day
     } else {
         println!("Input the day to get the day's answer. (0 for all)");
 
@@ -24,7 +56,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         if input.starts_with("make") {
             let input = input.strip_prefix("make").unwrap();
             let day: i32 = input.trim().parse().expect("Should be a number after make");
-            return add_new_day(day);
+            return add_new_day(&config, day);
         }
 
         match input.trim_end().parse() {
@@ -39,14 +71,15 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     if num != 0 {
         let now = Instant::now();
-        get_day(num);
+        get_day(&config, num);
         let elapsed = now.elapsed();
         println!("Time taken for day {num}: {elapsed:?}");
     } else {
+        println!("Advent of Code {}", config.year);
         let now = Instant::now();
         for day in 1..=12 {
             println!("\nDay {}\n", day);
-            get_day(day);
+            get_day(&config, day);
         }
         let elapsed = now.elapsed();
         println!("Time taken for all days: {elapsed:?}");
@@ -55,20 +88,20 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn get_day(num: i32) {
+fn get_day(config: &Config, num: i32) {
     match num {
-        1 => get_answer(1, answers::day01::answer),
-        2 => get_answer(2, answers::day02::answer),
-        3 => get_answer(3, answers::day03::answer),
-        4 => get_answer(4, answers::day04::answer),
-        5 => get_answer(5, answers::day05::answer),
-        6 => get_answer(6, answers::day06::answer),
-        7 => get_answer(7, answers::day07::answer),
-        8 => get_answer(8, answers::day08::answer),
-        9 => get_answer(9, answers::day09::answer),
-        10 => get_answer(10, answers::day10::answer),
-        11 => get_answer(11, answers::day11::answer),
-        12 => get_answer(12, answers::day12::answer),
+        1 => get_answer(config, 1, answers::day01::answer),
+        2 => get_answer(config, 2, answers::day02::answer),
+        3 => get_answer(config, 3, answers::day03::answer),
+        4 => get_answer(config, 4, answers::day04::answer),
+        5 => get_answer(config, 5, answers::day05::answer),
+        6 => get_answer(config, 6, answers::day06::answer),
+        7 => get_answer(config, 7, answers::day07::answer),
+        8 => get_answer(config, 8, answers::day08::answer),
+        9 => get_answer(config, 9, answers::day09::answer),
+        10 => get_answer(config, 10, answers::day10::answer),
+        11 => get_answer(config, 11, answers::day11::answer),
+        12 => get_answer(config, 12, answers::day12::answer),
         _ => {
             eprintln!("Error: Day should exist");
             exit(2);
@@ -76,37 +109,38 @@ fn get_day(num: i32) {
     }
 }
 
-fn get_answer(day: i32, answer: impl Fn() -> Result<(), io::Error>) {
-    if let Err(e) = answer() {
+fn get_answer(config: &Config, day: i32, answer: impl Fn(&Config) -> Result<(), io::Error>) {
+    if let Err(e) = answer(config) {
         eprintln!("Error on Day {day}: {e}");
         exit(3);
     }
 }
 
-fn add_new_day(day: i32) -> Result<(), Box<dyn Error>> {
-    let source = "./src/answers/base.rs";
-    let dest_day = "day".to_string()
-        + (if day < 10 { "0" } else { "" } )
-        + &day.to_string();
-    let destination = "./src/answers/".to_string() + &dest_day + ".rs";
+fn add_new_day(config: &Config, day: i32) -> Result<(), Box<dyn Error>> {
+    let source = &config.template;
+    let destination = config.answer_path(day);
 
-    if Path::new(&destination).exists() { 
+    if destination.exists() { 
         panic!("Trying to overwrite existing day!!!");
     }
 
-    println!("Copying from {source} to {destination}");
+    println!("Copying from {} to {}", source.display(), destination.display());
 
-    fs::copy(source, destination)?;
+    let template = fs::read_to_string(source)?;
+    let Some(contents) = Config::fill_template(&template, day) else {
+        return Err(format!("Template should have a `{DAY_PLACEHOLDER}` line").into());
+    };
+    fs::write(&destination, contents)?;
 
     println!("Copy successful");
 
     let mut ans = fs::OpenOptions::new()
         .append(true)
-        .open("./src/answers.rs")?;
+        .open(config.answers_mod_path())?;
 
     println!("Answers open to add mod");
 
-    ans.write_all(&("pub mod ".to_string() + &dest_day + ";\n").into_bytes())?;
+    ans.write_all(&("pub mod ".to_string() + &Config::day_name(day) + ";\n").into_bytes())?;
 
     Ok(())
 }
@@ -1,5 +1,39 @@
 #![allow(unused_imports)]
-use std::{cmp::Reverse, collections::{BinaryHeap, HashSet, VecDeque}, error::Error as ErrorTrait, fmt::Display, ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign}, slice::{Iter, IterMut}};
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet, VecDeque}, convert::Infallible, error::Error as ErrorTrait, fmt::Display, hash::Hash, num::TryFromIntError, ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign}, slice::{Iter, IterMut}};
+
+use crate::search;
+
+mod arith;
+mod automaton;
+mod bits;
+mod display;
+mod distance;
+mod hex;
+mod image;
+mod matrix3;
+#[cfg(feature = "rayon")]
+mod par;
+mod pattern;
+mod peel;
+mod ray;
+mod region;
+mod resize;
+#[cfg(feature = "serde")]
+pub mod serial;
+mod sparse;
+mod view;
+mod wrap;
+pub use automaton::{Cycle, Neighborhood, RunStats};
+pub use bits::BitMatrix;
+pub use display::{DisplayOptions, MatrixDisplay};
+pub use distance::Owner;
+pub use hex::{Hex, HexMap};
+pub use image::{distinct_color, Palette, Rgb, SvgOptions};
+pub use matrix3::{Matrix3, Pos3};
+pub use ray::Ray;
+pub use region::{Connectivity, Region, RegionId};
+pub use sparse::SparseGrid;
+pub use view::{MatrixView, MatrixViewMut, Rect};
 
 
 #[derive(Debug)]
@@ -18,6 +52,7 @@ impl Display for Error {
 impl ErrorTrait for Error {}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "serial::RawMatrix<T>"))]
 pub struct Matrix<T> {
     vals: Vec<T>,
     rows: usize,
@@ -49,83 +84,322 @@ impl<T> Matrix<T> {
         Pos::iter(self.rows, self.cols).zip(self.vals.iter_mut())
     }
 
-    pub fn get(&self, pos: Pos) -> Option<&T> {
-        if pos.0 < 0 || pos.1 < 0 {
+    fn index_of<N: Coord>(&self, pos: Pos<N>) -> Option<usize> {
+        let (row, col) = pos.to_usize()?;
+        if row >= self.rows || col >= self.cols {
             return None;
         }
 
-        let row = pos.0 as usize;
-        let col = pos.1 as usize;
-        if row >= self.rows || col >= self.cols {
-            return None;
+        Some(row * self.cols + col)
+    }
+
+    pub fn get<N: Coord>(&self, pos: Pos<N>) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.vals[idx])
+    }
+
+    pub fn get_mut<N: Coord>(&mut self, pos: Pos<N>) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.vals[idx])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        let start = row * self.cols;
+        &self.vals[start..start + self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        let start = row * self.cols;
+        &mut self.vals[start..start + self.cols]
+    }
+
+    pub fn col(&self, col: usize) -> std::iter::StepBy<std::iter::Skip<Iter<'_, T>>> {
+        assert!(col < self.cols, "Column {col} out of bounds for {} columns", self.cols);
+        self.vals.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn col_mut(&mut self, col: usize) -> std::iter::StepBy<std::iter::Skip<IterMut<'_, T>>> {
+        assert!(col < self.cols, "Column {col} out of bounds for {} columns", self.cols);
+        self.vals.iter_mut().skip(col).step_by(self.cols)
+    }
+
+    pub fn rows_iter(&self) -> std::slice::Chunks<'_, T> {
+        self.vals.chunks(self.cols.max(1))
+    }
+
+    pub fn rows_iter_mut(&mut self) -> std::slice::ChunksMut<'_, T> {
+        self.vals.chunks_mut(self.cols.max(1))
+    }
+
+    pub fn cols_iter(&self) -> impl Iterator<Item = std::iter::StepBy<std::iter::Skip<Iter<'_, T>>>> {
+        (0..self.cols).map(|col| self.col(col))
+    }
+}
+
+impl<T> Matrix<T> {
+    /// In-bounds cells at `pos + offset` for every offset, in the given order.
+    pub fn neighbors_with<'a>(&'a self, pos: Pos, offsets: &'a [Pos]) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let next = pos + *offset;
+            self.get(next).map(|val| (next, val))
+        })
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_with(pos, &Pos::NEIGHBORS4)
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_with(pos, &Pos::NEIGHBORS8)
+    }
+
+    pub fn count_neighbors_with(&self, pos: Pos, offsets: &[Pos], mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.neighbors_with(pos, offsets).filter(|(_, val)| pred(val)).count()
+    }
+
+    pub fn count_neighbors4(&self, pos: Pos, pred: impl FnMut(&T) -> bool) -> usize {
+        self.count_neighbors_with(pos, &Pos::NEIGHBORS4, pred)
+    }
+
+    pub fn count_neighbors8(&self, pos: Pos, pred: impl FnMut(&T) -> bool) -> usize {
+        self.count_neighbors_with(pos, &Pos::NEIGHBORS8, pred)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    Transpose,
+    /// Mirrors across the anti-diagonal, from top-right to bottom-left.
+    AntiTranspose,
+    RotateCw,
+    RotateCcw,
+    Rotate180,
+    /// Mirrors left to right.
+    FlipHorizontal,
+    /// Mirrors top to bottom.
+    FlipVertical,
+}
+
+impl Transform {
+    /// The eight rotations and reflections of a rectangle.
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity, Transform::RotateCw, Transform::Rotate180, Transform::RotateCcw,
+        Transform::FlipHorizontal, Transform::FlipVertical, Transform::Transpose, Transform::AntiTranspose,
+    ];
+
+    /// Shape of the result for a `rows` x `cols` input.
+    pub fn dims(self, rows: usize, cols: usize) -> (usize, usize) {
+        match self {
+            Transform::Transpose | Transform::AntiTranspose | Transform::RotateCw | Transform::RotateCcw => (cols, rows),
+            Transform::Identity | Transform::Rotate180 | Transform::FlipHorizontal | Transform::FlipVertical => (rows, cols),
         }
+    }
 
-        Some(&self.vals[row * self.cols + col])
+    /// Position in a `rows` x `cols` input that ends up at `(row, col)`.
+    pub fn source(self, rows: usize, cols: usize, row: usize, col: usize) -> (usize, usize) {
+        match self {
+            Transform::Identity => (row, col),
+            Transform::Transpose => (col, row),
+            Transform::AntiTranspose => (rows - 1 - col, cols - 1 - row),
+            Transform::RotateCw => (rows - 1 - col, row),
+            Transform::RotateCcw => (col, cols - 1 - row),
+            Transform::Rotate180 => (rows - 1 - row, cols - 1 - col),
+            Transform::FlipHorizontal => (row, cols - 1 - col),
+            Transform::FlipVertical => (rows - 1 - row, col),
+        }
     }
+}
 
-    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
-        if pos.0 < 0 || pos.1 < 0 {
-            return None;
+impl<T> Matrix<T> {
+    pub fn transform_in_place(&mut self, transform: Transform) {
+        let (rows, cols) = transform.dims(self.rows, self.cols);
+        let source = |idx: usize| {
+            let (row, col) = transform.source(self.rows, self.cols, idx / cols, idx % cols);
+            row * self.cols + col
+        };
+
+        // Follow each cycle of the permutation, swapping the source into place
+        let mut done = vec![false; self.vals.len()];
+        for start in 0..self.vals.len() {
+            if done[start] { continue; }
+
+            let mut curr = start;
+            loop {
+                done[curr] = true;
+                let next = source(curr);
+                if next == start { break; }
+
+                self.vals.swap(curr, next);
+                curr = next;
+            }
         }
 
-        let row = pos.0 as usize;
-        let col = pos.1 as usize;
-        if row >= self.rows || col >= self.cols {
-            return None;
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    pub fn transpose_in_place(&mut self) {
+        self.transform_in_place(Transform::Transpose);
+    }
+
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transform_in_place(Transform::RotateCw);
+    }
+
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transform_in_place(Transform::RotateCcw);
+    }
+
+    pub fn rotate_180_in_place(&mut self) {
+        self.transform_in_place(Transform::Rotate180);
+    }
+
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.transform_in_place(Transform::FlipHorizontal);
+    }
+
+    pub fn flip_vertical_in_place(&mut self) {
+        self.transform_in_place(Transform::FlipVertical);
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn transform(&self, transform: Transform) -> Self {
+        let (rows, cols) = transform.dims(self.rows, self.cols);
+        let vals = Pos::iter(rows, cols)
+            .map(|pos| {
+                let (row, col) = transform.source(self.rows, self.cols, pos.0 as usize, pos.1 as usize);
+                self.vals[row * self.cols + col].clone()
+            })
+            .collect();
+
+        Self { vals, rows, cols }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transform(Transform::Transpose)
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        self.transform(Transform::RotateCw)
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.transform(Transform::RotateCcw)
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.transform(Transform::Rotate180)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.transform(Transform::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.transform(Transform::FlipVertical)
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Builds a matrix from text, one row per line, mapping every char
+    /// through `parse`. Accepts `\n` or `\r\n` line endings and ignores
+    /// trailing newlines.
+    pub fn parse_with<E>(
+        input: &str,
+        mut parse: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError<E>> {
+        let input = input.trim_end_matches(['\n', '\r']);
+        let mut vals = Vec::with_capacity(input.len());
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let start = vals.len();
+            for (col, chr) in line.chars().enumerate() {
+                match parse(chr) {
+                    Ok(val) => vals.push(val),
+                    Err(err) => return Err(ParseError::Cell { pos: Pos(row as i32, col as i32), chr, err }),
+                }
+            }
+
+            let found = vals.len() - start;
+            if row == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(ParseError::RaggedRow { row, expected: cols, found });
+            }
+            rows += 1;
         }
 
-        Some(&mut self.vals[row * self.cols + col])
+        Ok(Self { vals, rows, cols })
     }
 }
 
-impl TryFrom<String> for Matrix<char> {
-    type Error = &'static str;
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError<E> {
+    RaggedRow { row: usize, expected: usize, found: usize },
+    Cell { pos: Pos, chr: char, err: E },
+}
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let cols = match value.find('\n') {
-            Some(pos) => pos,
-            None => return Err("Couldn't split string into rows"),
-        };
-        let values: Vec<char> = value.replace("\n", "").chars().collect();
-        
-        match (values, cols).try_into() {
-            Ok(matrix) => Ok(matrix),
-            Err(err) => match err {
-                Error::InvalidSize => Err("Couldn't create a matrix from a vector with this size"),
+impl<E: Display> Display for ParseError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // Lines and columns counted from 1, as editors show them
+            ParseError::RaggedRow { row, expected, found } => {
+                write!(f, "Line {} has {found} columns, expected {expected}", row + 1)
+            },
+            ParseError::Cell { pos, chr, err } => {
+                write!(f, "Couldn't parse '{chr}' at line {}, column {}: {err}", pos.0 + 1, pos.1 + 1)
             },
         }
     }
 }
 
+impl<E: std::fmt::Debug + Display> ErrorTrait for ParseError<E> {}
+
+impl TryFrom<&str> for Matrix<char> {
+    type Error = ParseError<Infallible>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Matrix::parse_with(value, Ok)
+    }
+}
+
+impl TryFrom<String> for Matrix<char> {
+    type Error = ParseError<Infallible>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.as_str().try_into()
+    }
+}
+
 impl<T> Matrix<T> {
-    pub fn astar(
+    /// Shortest path from `start` to `target` and its cost. The path starts
+    /// at `start` and stops before `target`, one position per step taken.
+    /// `move_options` yields the reachable positions with the cost of each
+    /// step and `heuristic` must never overestimate the remaining cost.
+    pub fn astar<C, I>(
         &self,
         start: Pos,
         target: Pos,
-        mut move_options: impl FnMut(&Self, &Pos) -> Vec<(Reverse<i64>, Pos)>,
-        heuristic: impl Fn(&Pos, &Pos) -> i64,
-    ) -> (Vec<Pos>, i64) {
-        let mut visited = HashSet::new();
-        visited.insert(start);
-
-        let mut next_moves = BinaryHeap::with_capacity(heuristic(&start, &target) as usize);
-        next_moves.extend(
-            move_options(self, &start).into_iter()
-                .map(|(cost, pos)| (Reverse(cost.0 + heuristic(&pos, &target)), pos, vec![start]))
+        mut move_options: impl FnMut(&Self, &Pos) -> I,
+        heuristic: impl Fn(&Pos, &Pos) -> C,
+    ) -> Option<(Vec<Pos>, C)>
+    where
+        C: Ord + Add<Output = C> + Default + Copy,
+        I: IntoIterator<Item = (C, Pos)>,
+    {
+        let search = search::astar(
+            [start],
+            |pos| move_options(self, pos).into_iter().map(|(cost, next)| (next, cost)),
+            |pos| heuristic(pos, &target),
+            |pos| *pos == target,
         );
 
-        while let Some((cost, pos, mut curr_path)) = next_moves.pop() {
-            if pos == target { return (curr_path, cost.0); }
-            if !visited.insert(pos) { continue; }
-
-            curr_path.push(pos);
-            next_moves.extend(
-                move_options(self, &pos).into_iter()
-                    .map(|(move_cost, pos)| (Reverse(move_cost.0 + cost.0 + heuristic(&pos, &target)), pos, curr_path.clone()))
-            );
-        }
-
-        (Vec::new(), -1)
+        let cost = search.goal_cost()?;
+        let mut path = search.path(&target)?;
+        path.pop();
+        Some((path, cost))
     }
 }
 
@@ -149,17 +423,11 @@ impl <T: Clone + PartialEq> Matrix<T> {
     }
 
     pub fn sq_floodfill(&mut self, start: Pos, remove: T, fill: T) {
-        let mov_opts = [
-            Pos(0,-1),
-            Pos(0, 1),
-            Pos(-1,0),
-            Pos( 1,0),
-        ];
+        let mov_opts = Pos::NEIGHBORS4;
 
         let mut visited = Matrix::with_default(self.rows, self.cols, false);
 
-        let mut queue = Vec::new();
-        queue.extend(mov_opts.map(|offset| start + offset));
+        let mut queue = vec![start];
 
         while let Some(pos) = queue.pop() {
             if let Some(visit) = visited.get(pos) {
@@ -174,18 +442,18 @@ impl <T: Clone + PartialEq> Matrix<T> {
     }
 }
 
-impl<T> Index<Pos> for Matrix<T> {
+impl<T, N: Coord> Index<Pos<N>> for Matrix<T> {
     type Output = T;
-    fn index(&self, index: Pos) -> &Self::Output {
-        let (row, col) = index.try_into().unwrap();
-        &self.vals[row * self.cols + col]
+    fn index(&self, index: Pos<N>) -> &Self::Output {
+        let idx = self.index_of(index).expect("Position should be inside the matrix");
+        &self.vals[idx]
     }
 }
 
-impl<T> IndexMut<Pos> for Matrix<T> {
-    fn index_mut(&mut self, index: Pos) -> &mut Self::Output {
-        let (row, col) = index.try_into().unwrap();
-        &mut self.vals[row * self.cols + col]
+impl<T, N: Coord> IndexMut<Pos<N>> for Matrix<T> {
+    fn index_mut(&mut self, index: Pos<N>) -> &mut Self::Output {
+        let idx = self.index_of(index).expect("Position should be inside the matrix");
+        &mut self.vals[idx]
     }
 }
 
@@ -210,79 +478,164 @@ impl<T> IntoIterator for Matrix<T> {
     }
 }
 
-impl<T: Clone + Display> Display for Matrix<T> {
+impl<T: Display> Display for Matrix<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.rows {
-            let start = i*self.cols;
-            let row: Vec<T> = self.vals[start..start+self.cols].to_vec();
-            for item in row {
-                write!(f, "{item} ")?;
-            }
-            writeln!(f)?
-        }
-
-        Ok(())
+        self.display_with(DisplayOptions::default()).fmt(f)
     }
 }
 
-pub fn manhattan_distance(from: &Pos, to: &Pos) -> i64 {
-    to.0.abs_diff(from.0) as i64 + to.1.abs_diff(from.1) as i64
+pub fn manhattan_distance<N: Coord>(from: &Pos<N>, to: &Pos<N>) -> N::Wide {
+    from.manhattan_distance(to)
 }
 
+/// Signed integer usable as a [`Pos`] coordinate.
+pub trait Coord:
+    Copy + Default + Ord + Hash + std::fmt::Debug + Display
+    + Add<Output = Self> + AddAssign + Sub<Output = Self> + SubAssign + Mul<Output = Self> + Neg<Output = Self>
+    + TryFrom<usize, Error = TryFromIntError> + TryInto<usize, Error = TryFromIntError>
+{
+    /// Unsigned type of the same width, which fits any distance between
+    /// two coordinates.
+    type Unsigned: Copy + Default + Ord + Hash + std::fmt::Debug + Display + Add<Output = Self::Unsigned>;
+
+    /// Unsigned type twice as wide, which fits sums of a few distances.
+    /// `i128` has none wider and uses `u128`.
+    type Wide: Copy + Default + Ord + Hash + std::fmt::Debug + Display + Add<Output = Self::Wide>;
+
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+
+    /// [`Coord::abs_diff`] as the wide type.
+    fn wide_abs_diff(self, other: Self) -> Self::Wide;
+
+    fn to_i128(self) -> i128;
+
+    /// `None` if `val` is out of range.
+    fn from_i128(val: i128) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($ty:ty => $unsigned:ty, $wide:ty);*) => {$(
+        impl Coord for $ty {
+            type Unsigned = $unsigned;
+            type Wide = $wide;
+
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$ty>::abs(self)
+            }
+
+            fn abs_diff(self, other: Self) -> $unsigned {
+                <$ty>::abs_diff(self, other)
+            }
+
+            fn wide_abs_diff(self, other: Self) -> $wide {
+                <$ty>::abs_diff(self, other) as $wide
+            }
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(val: i128) -> Option<Self> {
+                val.try_into().ok()
+            }
+        }
+    )*};
+}
+
+impl_coord!(i8 => u8, u16; i16 => u16, u32; i32 => u32, u64; i64 => u64, u128; i128 => u128, u128; isize => usize, u128);
+
+/// Position as `(row, col)` for matrices, or any pair of coordinates.
+/// Matrices index with any [`Coord`] width, wider types avoid overflow in
+/// coordinate math.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Pos(pub i32, pub i32);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pos<N = i32>(pub N, pub N);
 
 impl Pos {
+    pub const NEIGHBORS4: [Pos; 4] = [Pos(-1, 0), Pos(0, 1), Pos(1, 0), Pos(0, -1)];
+    pub const NEIGHBORS8: [Pos; 8] = [
+        Pos(-1,-1), Pos(-1, 0), Pos(-1, 1),
+        Pos( 0,-1),             Pos( 0, 1),
+        Pos( 1,-1), Pos( 1, 0), Pos( 1, 1),
+    ];
+
     pub fn iter(rows: usize, cols: usize) -> PosIter {
         PosIter::new(rows, cols)
     }
 }
 
-impl Display for Pos {
+impl<N: Coord> Pos<N> {
+    /// `(row, col)` as indices, `None` if either is negative or too large.
+    pub fn to_usize(self) -> Option<(usize, usize)> {
+        Some((self.0.try_into().ok()?, self.1.try_into().ok()?))
+    }
+
+    /// The same position with another coordinate type, `None` if it
+    /// doesn't fit.
+    pub fn cast<M: Coord>(self) -> Option<Pos<M>> {
+        Some(Pos(M::from_i128(self.0.to_i128())?, M::from_i128(self.1.to_i128())?))
+    }
+
+    /// Distance along each axis, unsigned so that it can't overflow.
+    pub fn abs_diff(&self, other: &Pos<N>) -> Pos<N::Unsigned> {
+        Pos(self.0.abs_diff(other.0), self.1.abs_diff(other.1))
+    }
+
+    /// Summed in the wide type, so it can't overflow except for `i128`.
+    pub fn manhattan_distance(&self, other: &Pos<N>) -> N::Wide {
+        self.0.wide_abs_diff(other.0) + self.1.wide_abs_diff(other.1)
+    }
+}
+
+impl<N: Display> Display for Pos<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({},{})", self.0, self.1)
     }
 }
 
-impl Add<Pos> for Pos {
-    type Output = Pos;
+impl<N: Coord> Add<Pos<N>> for Pos<N> {
+    type Output = Pos<N>;
 
-    fn add(self, rhs: Pos) -> Self::Output {
+    fn add(self, rhs: Pos<N>) -> Self::Output {
         Pos(self.0 + rhs.0, self.1 + rhs.1)
     }
 }
 
-impl AddAssign<Pos> for Pos {
-    fn add_assign(&mut self, rhs: Pos) {
+impl<N: Coord> AddAssign<Pos<N>> for Pos<N> {
+    fn add_assign(&mut self, rhs: Pos<N>) {
         self.0 += rhs.0;
         self.1 += rhs.1;
     }
 }
 
-impl Sub<Pos> for Pos {
-    type Output = Pos;
+impl<N: Coord> Sub<Pos<N>> for Pos<N> {
+    type Output = Pos<N>;
 
-    fn sub(self, rhs: Pos) -> Self::Output {
+    fn sub(self, rhs: Pos<N>) -> Self::Output {
         Pos(self.0 - rhs.0, self.1 - rhs.1)
     }
 }
 
-impl SubAssign<Pos> for Pos {
-    fn sub_assign(&mut self, rhs: Pos) {
+impl<N: Coord> SubAssign<Pos<N>> for Pos<N> {
+    fn sub_assign(&mut self, rhs: Pos<N>) {
         self.0 -= rhs.0;
         self.1 -= rhs.1;
     }
 }
 
-impl TryFrom<Pos> for (usize, usize) {
-    type Error = &'static str;
+impl<N: Coord> TryFrom<Pos<N>> for (usize, usize) {
+    type Error = TryFromIntError;
 
-    fn try_from(value: Pos) -> Result<Self, Self::Error> {
-        if value.0 < 0 || value.1 < 0 {
-            Err("Value is not positive")
-        } else {
-            Ok((value.0 as usize, value.1 as usize))
-        }
+    fn try_from(value: Pos<N>) -> Result<Self, Self::Error> {
+        Ok((value.0.try_into()?, value.1.try_into()?))
     }
 }
 
@@ -333,6 +686,7 @@ mod tests {
 
     #[test]
     fn test_astar_2024_18_part1_example() {
+        // The first 12 bytes to fall
         let coordinates = "\
 5,4
 4,2
@@ -345,49 +699,180 @@ mod tests {
 0,6
 3,3
 2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
+5,1";
 
         let mut matrix = Matrix::with_default(7, 7, '.');
-        let corrupted = 12;
-        for coord in coordinates.lines().take(corrupted) {
-            let mut pair = coord.split(',');
-            let col = pair.next().unwrap().parse().unwrap();
-            let row = pair.next().unwrap().parse().unwrap();
-
-            matrix[Pos(row, col)] = '#';
+        for pos in SparseGrid::from_points(coordinates).unwrap().positions() {
+            matrix[*pos] = '#';
         }
 
-        let (path, _) = matrix.astar(
+        let (path, cost) = matrix.astar(
             Pos(0,0), 
             Pos(6,6), 
             |mat, pos| {
-                let pos = *pos;
-                vec![
-                    (Reverse(1), pos + Pos(0, 1)),
-                    (Reverse(1), pos + Pos(0,-1)),
-                    (Reverse(1), pos + Pos( 1,0)),
-                    (Reverse(1), pos + Pos(-1,0)),
-                ].into_iter().filter(|pair| {
-                        mat.get(pair.1).is_some_and(|chr| *chr!='#')
-                    })
-                    .collect()
+                mat.neighbors4(*pos)
+                    .filter(|(_, chr)| **chr != '#')
+                    .map(|(pos, _)| (1, pos))
+                    .collect::<Vec<_>>()
             },
             manhattan_distance,
-        );
+        ).unwrap();
 
         assert_eq!(path.len(), 22);
+        assert_eq!(cost, 22);
+        assert_eq!(path[0], Pos(0,0));
+        assert_eq!(manhattan_distance(&path[21], &Pos(6,6)), 1);
+        assert!(path.windows(2).all(|step| manhattan_distance(&step[0], &step[1]) == 1));
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let matrix: Matrix<char> = "..#\n.#.\n#..".try_into().unwrap();
+
+        let res = matrix.astar(
+            Pos(0,0),
+            Pos(2,2),
+            |mat, pos| mat.neighbors4(*pos).filter(|(_, chr)| **chr != '#').map(|(pos, _)| (1u64, pos)).collect::<Vec<_>>(),
+            manhattan_distance,
+        );
+
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn test_astar_admissible_inconsistent_heuristic() {
+        let matrix = Matrix::with_default(1, 5, '.');
+        let (s, a, b, c, g) = (Pos(0, 0), Pos(0, 1), Pos(0, 2), Pos(0, 3), Pos(0, 4));
+        // The cheap route through `a` is only explored after `c` was reached through `b`
+        let edges: HashMap<Pos, Vec<(u32, Pos)>> = HashMap::from([
+            (s, vec![(1, a), (1, b)]),
+            (a, vec![(1, c)]),
+            (b, vec![(3, c)]),
+            (c, vec![(10, g)]),
+        ]);
+
+        let (path, cost) = matrix.astar(
+            s,
+            g,
+            |_, pos| edges.get(pos).cloned().unwrap_or_default(),
+            |pos, _| if *pos == a { 11 } else { 0 },
+        ).unwrap();
+
+        assert_eq!(cost, 12);
+        assert_eq!(path, [s, a, c]);
+    }
+
+    #[test]
+    fn test_sq_floodfill_fills_start() {
+        let mut grid: Matrix<char> = "..#\n.##\n#..".try_into().unwrap();
+
+        grid.sq_floodfill(Pos(0, 0), '.', 'o');
+
+        let expected: Matrix<char> = "oo#\no##\n#..".try_into().unwrap();
+        assert!(grid.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let digits = Matrix::parse_with("123\r\n456\r\n\r\n", |c| c.to_digit(10).map(|d| d as u8).ok_or(c)).unwrap();
+        assert_eq!((digits.rows(), digits.cols()), (2, 3));
+        assert_eq!(digits[Pos(1, 2)], 6);
+
+        let walls = Matrix::parse_with("#.#", |c| Ok::<_, Infallible>(c == '#')).unwrap();
+        assert_eq!((walls.rows(), walls.cols()), (1, 3));
+        assert!(walls[Pos(0, 2)]);
+
+        let multibyte: Matrix<char> = "·é·\n#·#\n".try_into().unwrap();
+        assert_eq!((multibyte.rows(), multibyte.cols()), (2, 3));
+        assert_eq!(multibyte[Pos(0, 1)], 'é');
+    }
+
+    #[test]
+    fn test_parse_with_errors() {
+        let ragged = Matrix::<char>::try_from("...\n..\n...").unwrap_err();
+        assert_eq!(ragged, ParseError::RaggedRow { row: 1, expected: 3, found: 2 });
+        assert_eq!(ragged.to_string(), "Line 2 has 2 columns, expected 3");
+
+        let bad_digit = Matrix::parse_with("12\n3x", |c| c.to_digit(10).ok_or("not a digit")).unwrap_err();
+        assert_eq!(bad_digit, ParseError::Cell { pos: Pos(1, 1), chr: 'x', err: "not a digit" });
+        assert_eq!(bad_digit.to_string(), "Couldn't parse 'x' at line 2, column 2: not a digit");
+    }
+
+    #[test]
+    fn test_rows_and_cols() {
+        let matrix: Matrix<char> = "abc\ndef".try_into().unwrap();
+
+        assert_eq!(matrix.row(1), &['d', 'e', 'f']);
+        assert_eq!(matrix.col(1).copied().collect::<String>(), "be");
+        assert_eq!(matrix.rows_iter().count(), 2);
+
+        let cols: Vec<String> = matrix.cols_iter().map(|col| col.collect()).collect();
+        assert_eq!(cols, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let matrix: Matrix<char> = "@.@\n.@.\n@@.".try_into().unwrap();
+
+        let corner: Vec<Pos> = matrix.neighbors8(Pos(0, 0)).map(|(pos, _)| pos).collect();
+        assert_eq!(corner, [Pos(0, 1), Pos(1, 0), Pos(1, 1)]);
+        assert_eq!(matrix.neighbors4(Pos(1, 1)).count(), 4);
+        assert_eq!(matrix.count_neighbors8(Pos(1, 1), |c| *c == '@'), 4);
+        assert_eq!(matrix.count_neighbors4(Pos(1, 1), |c| *c == '@'), 1);
+        assert_eq!(matrix.count_neighbors_with(Pos(2, 2), &[Pos(0, -2), Pos(-2, 0)], |c| *c == '@'), 2);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let matrix: Matrix<char> = "abc\ndef".try_into().unwrap();
+        let expected = [
+            (Transform::Identity, "abc\ndef"),
+            (Transform::Transpose, "ad\nbe\ncf"),
+            (Transform::AntiTranspose, "fc\neb\nda"),
+            (Transform::RotateCw, "da\neb\nfc"),
+            (Transform::RotateCcw, "cf\nbe\nad"),
+            (Transform::Rotate180, "fed\ncba"),
+            (Transform::FlipHorizontal, "cba\nfed"),
+            (Transform::FlipVertical, "def\nabc"),
+        ];
+
+        for (transform, text) in expected {
+            let expected: Matrix<char> = text.try_into().unwrap();
+            let copied = matrix.transform(transform);
+            let mut in_place = matrix.clone();
+            in_place.transform_in_place(transform);
+
+            assert_eq!((copied.rows(), copied.cols()), (expected.rows(), expected.cols()));
+            assert!(copied.iter().eq(expected.iter()), "{transform:?}");
+            assert!(in_place.iter().eq(expected.iter()), "{transform:?} in place");
+            assert_eq!((in_place.rows(), in_place.cols()), (expected.rows(), expected.cols()));
+        }
+
+        assert!(matrix.rotate_cw().rotate_ccw().iter().eq(matrix.iter()));
+    }
+
+    #[test]
+    fn test_pos_widths() {
+        let far = Pos(3_000_000_000i64, -5);
+
+        assert_eq!(far.abs_diff(&Pos(0, 5)), Pos(3_000_000_000, 10));
+        assert_eq!(far.manhattan_distance(&Pos(0, 0)), 3_000_000_005);
+        assert_eq!(Pos(i32::MAX, 0).manhattan_distance(&Pos(-1, 0)), 1 << 31);
+        assert_eq!(Pos(i32::MIN, i32::MIN).manhattan_distance(&Pos(i32::MAX, i32::MAX)), 2 * u32::MAX as u64);
+        assert_eq!(manhattan_distance(&Pos(i64::MIN, i64::MAX), &Pos(i64::MAX, i64::MIN)), 2 * u64::MAX as u128);
+        assert_eq!(Pos(i8::MIN, i8::MAX).abs_diff(&Pos(i8::MAX, i8::MAX)), Pos(u8::MAX, 0));
+        assert_eq!(far.cast::<i32>(), None);
+        assert_eq!(Pos(2i64, 1).cast::<i32>(), Some(Pos(2, 1)));
+        assert_eq!(Pos(-128i16, 127).cast::<i8>(), Some(Pos(-128, 127)));
+
+        assert_eq!(Pos(1i64, 2).to_usize(), Some((1, 2)));
+        assert_eq!(Pos(-1i8, 2).to_usize(), None);
+        assert!(<(usize, usize)>::try_from(Pos(0, -1)).is_err());
+
+        let matrix: Matrix<char> = "ab\ncd".try_into().unwrap();
+        assert_eq!(matrix[Pos(1i64, 0)], 'c');
+        assert_eq!(matrix.get(Pos(0i8, 1)), Some(&'b'));
+        assert_eq!(matrix.get(Pos(0i64, 2)), None);
+        assert_eq!(matrix.get(Pos(far.0, 0)), None);
     }
 }
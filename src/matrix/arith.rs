@@ -0,0 +1,201 @@
+use std::ops::{Add, Mul, Rem, Sub};
+
+use super::Matrix;
+
+impl<T> Matrix<T> {
+    fn zip_with<U>(&self, other: &Matrix<T>, op: impl Fn(&T, &T) -> U) -> Matrix<U> {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols), "Matrices should have the same shape");
+        Matrix {
+            vals: self.vals.iter().zip(&other.vals).map(|(a, b)| op(a, b)).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    fn map_vals<U>(&self, op: impl Fn(&T) -> U) -> Matrix<U> {
+        Matrix { vals: self.vals.iter().map(op).collect(), rows: self.rows, cols: self.cols }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Matrix<T> {
+    /// Element-wise product.
+    pub fn hadamard(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.zip_with(other, |a, b| *a * *b)
+    }
+
+    pub fn scalar_mul(&self, k: T) -> Matrix<T> {
+        self.map_vals(|val| *val * k)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Matrix<T> {
+    pub fn scalar_add(&self, k: T) -> Matrix<T> {
+        self.map_vals(|val| *val + k)
+    }
+}
+
+impl<T: Copy + Default + From<u8>> Matrix<T> {
+    pub fn identity(size: usize) -> Self {
+        let mut vals = vec![T::default(); size * size];
+        for idx in 0..size {
+            vals[idx * size + idx] = T::from(1);
+        }
+        Self { vals, rows: size, cols: size }
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> Matrix<T> {
+    /// Matrix product, reducing every sum with `reduce`.
+    fn product_with(&self, other: &Matrix<T>, reduce: impl Fn(T) -> T) -> Matrix<T> {
+        assert_eq!(self.cols, other.rows, "Left columns should match right rows");
+        let mut vals = vec![T::default(); self.rows * other.cols];
+        for (row, out) in self.rows_iter().zip(vals.chunks_mut(other.cols.max(1))) {
+            for (a, other_row) in row.iter().zip(other.rows_iter()) {
+                for (out, b) in out.iter_mut().zip(other_row) {
+                    *out = reduce(*out + *a * *b);
+                }
+            }
+        }
+        Matrix { vals, rows: self.rows, cols: other.cols }
+    }
+
+    pub fn matmul(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.product_with(other, |val| val)
+    }
+
+    /// `self.transpose().matmul(other)` without building the transpose.
+    pub fn transpose_mul(&self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.rows, other.rows, "Both matrices should have the same rows");
+        let mut vals = vec![T::default(); self.cols * other.cols];
+        for (row, other_row) in self.rows_iter().zip(other.rows_iter()) {
+            for (a, out) in row.iter().zip(vals.chunks_mut(other.cols.max(1))) {
+                for (out, b) in out.iter_mut().zip(other_row) {
+                    *out = *out + *a * *b;
+                }
+            }
+        }
+        Matrix { vals, rows: self.cols, cols: other.cols }
+    }
+}
+
+impl<T: Copy + Default + From<u8> + Add<Output = T> + Mul<Output = T>> Matrix<T> {
+    fn pow_with(&self, mut exp: u64, reduce: impl Fn(T) -> T) -> Matrix<T> {
+        assert_eq!(self.rows, self.cols, "Only square matrices have powers");
+        // Reduced up front so the first product can't overflow
+        let mut result = Matrix::identity(self.rows).map_vals(|val| reduce(*val));
+        let mut base = self.map_vals(|val| reduce(*val));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.product_with(&base, &reduce);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.product_with(&base, &reduce);
+            }
+        }
+        result
+    }
+
+    /// `self` multiplied by itself `exp` times, by repeated squaring.
+    ///
+    /// With an adjacency matrix, entry `(a, b)` counts the walks of exactly
+    /// `exp` steps from `a` to `b`.
+    pub fn pow(&self, exp: u64) -> Matrix<T> {
+        self.pow_with(exp, |val| val)
+    }
+}
+
+impl<T: Copy + Default + From<u8> + Add<Output = T> + Mul<Output = T> + Rem<Output = T>> Matrix<T> {
+    pub fn matmul_mod(&self, other: &Matrix<T>, modulus: T) -> Matrix<T> {
+        self.product_with(other, |val| val % modulus)
+    }
+
+    /// [`Matrix::pow`] with every entry kept below `modulus`.
+    pub fn pow_mod(&self, exp: u64, modulus: T) -> Matrix<T> {
+        self.pow_with(exp, |val| val % modulus)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Add<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Self::Output {
+        self.zip_with(rhs, |a, b| *a + *b)
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Self::Output {
+        self.zip_with(rhs, |a, b| *a - *b)
+    }
+}
+
+/// Matrix product, see [`Matrix::hadamard`] for the element-wise one.
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> Mul<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        self.matmul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Pos;
+
+    fn matrix(vals: Vec<i64>, cols: usize) -> Matrix<i64> {
+        (vals, cols).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_elementwise() {
+        let a = matrix(vec![1, 2, 3, 4], 2);
+        let b = matrix(vec![5, 6, 7, 8], 2);
+
+        assert_eq!((&a + &b).into_iter().collect::<Vec<_>>(), [6, 8, 10, 12]);
+        assert_eq!((&b - &a).into_iter().collect::<Vec<_>>(), [4, 4, 4, 4]);
+        assert_eq!(a.hadamard(&b).into_iter().collect::<Vec<_>>(), [5, 12, 21, 32]);
+        assert_eq!(a.scalar_mul(3).scalar_add(-1).into_iter().collect::<Vec<_>>(), [2, 5, 8, 11]);
+    }
+
+    #[test]
+    fn test_matmul() {
+        let a = matrix(vec![1, 2, 3, 4, 5, 6], 3);
+        let b = matrix(vec![7, 8, 9, 10, 11, 12], 2);
+
+        let product = &a * &b;
+        assert_eq!((product.rows(), product.cols()), (2, 2));
+        assert_eq!(product.into_iter().collect::<Vec<_>>(), [58, 64, 139, 154]);
+
+        let expected = a.transpose().matmul(&a);
+        assert!(a.transpose_mul(&a).iter().eq(expected.iter()));
+        assert!(a.matmul(&Matrix::identity(3)).iter().eq(a.iter()));
+
+        let empty: Matrix<i64> = Matrix::new(3, 0);
+        let product = a.matmul(&empty);
+        assert_eq!((product.rows(), product.cols()), (2, 0));
+        let product = a.transpose().transpose_mul(&empty);
+        assert_eq!((product.rows(), product.cols()), (2, 0));
+    }
+
+    #[test]
+    fn test_pow() {
+        let fib = matrix(vec![1, 1, 1, 0], 2);
+
+        assert_eq!(fib.pow(0).into_iter().collect::<Vec<_>>(), [1, 0, 0, 1]);
+        assert_eq!(fib.pow(10)[Pos(0, 1)], 55);
+        assert_eq!(fib.pow_mod(90, 1_000_000_007)[Pos(0, 1)], 2_880_067_194_370_816_120 % 1_000_000_007);
+
+        // Squaring these entries unreduced would overflow an i64
+        let big = matrix(vec![4_000_000_000, 0, 0, 1], 2);
+        assert_eq!(big.pow_mod(2, 1_000_000_007)[Pos(0, 0)], 784);
+        assert_eq!(fib.pow_mod(0, 1).into_iter().collect::<Vec<_>>(), [0, 0, 0, 0]);
+
+        // a -> b, a -> c, b -> c, c -> a: walks of exactly 4 steps from a to c
+        let graph = matrix(vec![0, 1, 1, 0, 0, 1, 1, 0, 0], 3);
+        assert_eq!(graph.pow(4)[Pos(0, 2)], 2);
+    }
+}
@@ -0,0 +1,174 @@
+use std::{collections::HashMap, hash::Hash, mem};
+
+use super::{Matrix, Pos};
+
+/// A cell and the matrix around it, as seen by a [`Matrix::step`] rule.
+/// Always reads the previous generation.
+#[derive(Debug)]
+pub struct Neighborhood<'a, T> {
+    pub pos: Pos,
+    matrix: &'a Matrix<T>,
+}
+
+impl<'a, T> Neighborhood<'a, T> {
+    pub fn cell(&self) -> &'a T {
+        &self.matrix[self.pos]
+    }
+
+    /// Cell at `pos + offset`, if in bounds.
+    pub fn get(&self, offset: Pos) -> Option<&'a T> {
+        self.matrix.get(self.pos + offset)
+    }
+
+    pub fn neighbors4(&self) -> impl Iterator<Item = (Pos, &'a T)> + use<'a, T> {
+        self.matrix.neighbors4(self.pos)
+    }
+
+    pub fn neighbors8(&self) -> impl Iterator<Item = (Pos, &'a T)> + use<'a, T> {
+        self.matrix.neighbors8(self.pos)
+    }
+
+    pub fn count4(&self, pred: impl FnMut(&T) -> bool) -> usize {
+        self.matrix.count_neighbors4(self.pos, pred)
+    }
+
+    pub fn count8(&self, pred: impl FnMut(&T) -> bool) -> usize {
+        self.matrix.count_neighbors8(self.pos, pred)
+    }
+}
+
+/// The grid repeats every `period` generations from generation `start` on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunStats {
+    /// Generations the grid advanced by, including skipped whole cycles.
+    pub generations: usize,
+    /// Cells changed by each simulated generation.
+    pub changes: Vec<usize>,
+    pub cycle: Option<Cycle>,
+}
+
+impl RunStats {
+    pub fn total_changes(&self) -> usize {
+        self.changes.iter().sum()
+    }
+}
+
+impl<T: PartialEq> Matrix<T> {
+    fn step_into(&self, back: &mut Vec<T>, rule: &mut impl FnMut(Neighborhood<T>) -> T) -> usize {
+        back.clear();
+        back.extend(Pos::iter(self.rows, self.cols).map(|pos| rule(Neighborhood { pos, matrix: self })));
+
+        self.vals.iter().zip(back.iter()).filter(|(old, new)| old != new).count()
+    }
+
+    /// Replaces every cell by `rule` applied to the current generation.
+    /// Returns how many cells changed.
+    pub fn step(&mut self, mut rule: impl FnMut(Neighborhood<T>) -> T) -> usize {
+        let mut back = Vec::with_capacity(self.vals.len());
+        let changes = self.step_into(&mut back, &mut rule);
+        self.vals = back;
+
+        changes
+    }
+}
+
+impl<T: PartialEq + Eq + Hash + Clone> Matrix<T> {
+    /// Steps until a generation changes nothing or the grid repeats an
+    /// earlier state.
+    pub fn run_until_stable(&mut self, rule: impl FnMut(Neighborhood<T>) -> T) -> RunStats {
+        self.run(None, rule)
+    }
+
+    /// Advances exactly `n` generations, skipping ahead once a cycle is found.
+    pub fn run_n(&mut self, n: usize, rule: impl FnMut(Neighborhood<T>) -> T) -> RunStats {
+        self.run(Some(n), rule)
+    }
+
+    fn run(&mut self, limit: Option<usize>, mut rule: impl FnMut(Neighborhood<T>) -> T) -> RunStats {
+        let mut stats = RunStats::default();
+        let mut seen = HashMap::from([(self.vals.clone(), 0)]);
+        let mut back = Vec::with_capacity(self.vals.len());
+
+        while limit.is_none_or(|n| stats.generations < n) {
+            let changes = self.step_into(&mut back, &mut rule);
+            if changes == 0 && limit.is_none() { break; }
+
+            mem::swap(&mut self.vals, &mut back);
+            stats.generations += 1;
+            stats.changes.push(changes);
+
+            if stats.cycle.is_some() { continue; }
+            if let Some(&start) = seen.get(&self.vals) {
+                let period = stats.generations - start;
+                stats.cycle = Some(Cycle { start, period });
+                match limit {
+                    None => break,
+                    Some(n) => stats.generations = n - (n - stats.generations) % period,
+                }
+            } else {
+                seen.insert(self.vals.clone(), stats.generations);
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: Neighborhood<bool>) -> bool {
+        let alive = cell.count8(|alive| *alive);
+        alive == 3 || (*cell.cell() && alive == 2)
+    }
+
+    fn parse(grid: &str) -> Matrix<bool> {
+        Matrix::parse_with(grid, |c| Ok::<_, ()>(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_step() {
+        let mut grid = parse(".....\n..#..\n..#..\n..#..\n.....");
+
+        let changes = grid.step(life);
+
+        assert_eq!(changes, 4);
+        assert!(grid.iter().eq(parse(".....\n.....\n.###.\n.....\n.....").iter()));
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut block = parse("....\n.##.\n.#..\n....");
+        let stats = block.run_until_stable(life);
+        assert_eq!(stats.generations, 1);
+        assert_eq!(stats.changes, [1]);
+        assert_eq!(stats.cycle, None);
+
+        let mut blinker = parse(".....\n..#..\n..#..\n..#..\n.....");
+        let stats = blinker.run_until_stable(life);
+        assert_eq!(stats.cycle, Some(Cycle { start: 0, period: 2 }));
+        assert_eq!(stats.generations, 2);
+    }
+
+    #[test]
+    fn test_run_n_skips_cycles() {
+        let start = parse(".....\n..#..\n..#..\n..#..\n.....");
+
+        let mut odd = start.clone();
+        let stats = odd.run_n(1_000_001, life);
+        assert_eq!(stats.generations, 1_000_001);
+        assert_eq!(stats.changes.len(), 3);
+        assert!(odd.iter().eq(parse(".....\n.....\n.###.\n.....\n.....").iter()));
+
+        let mut even = start.clone();
+        even.run_n(1_000_000, life);
+        assert!(even.iter().eq(start.iter()));
+    }
+}
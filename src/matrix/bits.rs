@@ -0,0 +1,418 @@
+use std::fmt::Display;
+
+use super::{Matrix, Pos};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Boolean grid packing each row into `u64` words, bit `c % 64` of word
+/// `c / 64` holding column `c`. Bits past the last column are kept clear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    words: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    row_words: usize,
+}
+
+impl BitMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let row_words = cols.div_ceil(WORD_BITS);
+        Self { words: vec![0; rows * row_words], rows, cols, row_words }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn index(&self, pos: Pos) -> Option<(usize, u64)> {
+        let (row, col) = pos.to_usize()?;
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+
+        Some((row * self.row_words + col / WORD_BITS, 1 << (col % WORD_BITS)))
+    }
+
+    /// `false` outside the matrix.
+    pub fn get(&self, pos: Pos) -> bool {
+        self.index(pos).is_some_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    pub fn set(&mut self, pos: Pos, val: bool) {
+        let (word, mask) = self.index(pos).expect("Position should be inside the matrix");
+        if val {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        let start = row * self.row_words;
+        &self.words[start..start + self.row_words]
+    }
+
+    /// Words of `row`, for word-wise updates. Bits past the last column
+    /// should be left clear.
+    pub fn row_words_mut(&mut self, row: usize) -> &mut [u64] {
+        let start = row * self.row_words;
+        &mut self.words[start..start + self.row_words]
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn count_row_ones(&self, row: usize) -> usize {
+        self.row_words(row).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Positions of set bits in row-major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            self.row_words(row).iter().enumerate().flat_map(move |(idx, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 { return None; }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(Pos(row as i32, (idx * WORD_BITS + bit) as i32))
+                })
+            })
+        })
+    }
+
+    fn clear_padding(&mut self) {
+        let used = self.cols % WORD_BITS;
+        if used == 0 { return; }
+
+        let mask = (1u64 << used) - 1;
+        for row in 0..self.rows {
+            self.words[(row + 1) * self.row_words - 1] &= mask;
+        }
+    }
+
+    fn zip_with(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols), "Matrices should have the same shape");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, *other);
+        }
+    }
+
+    pub fn and_assign(&mut self, other: &Self) {
+        self.zip_with(other, |a, b| a & b);
+    }
+
+    pub fn or_assign(&mut self, other: &Self) {
+        self.zip_with(other, |a, b| a | b);
+    }
+
+    pub fn xor_assign(&mut self, other: &Self) {
+        self.zip_with(other, |a, b| a ^ b);
+    }
+
+    pub fn and_not_assign(&mut self, other: &Self) {
+        self.zip_with(other, |a, b| a & !b);
+    }
+
+    fn zip_row_with(&mut self, row: usize, other: &[u64], op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(other.len(), self.row_words, "Row should have the same number of words");
+        for (word, other) in self.row_words_mut(row).iter_mut().zip(other) {
+            *word = op(*word, *other);
+        }
+
+        let used = self.cols % WORD_BITS;
+        if used != 0 {
+            self.words[(row + 1) * self.row_words - 1] &= (1u64 << used) - 1;
+        }
+    }
+
+    /// [`BitMatrix::and_assign`] for a single row, `other` being packed
+    /// like [`BitMatrix::row_words`].
+    pub fn and_row_assign(&mut self, row: usize, other: &[u64]) {
+        self.zip_row_with(row, other, |a, b| a & b);
+    }
+
+    pub fn or_row_assign(&mut self, row: usize, other: &[u64]) {
+        self.zip_row_with(row, other, |a, b| a | b);
+    }
+
+    pub fn xor_row_assign(&mut self, row: usize, other: &[u64]) {
+        self.zip_row_with(row, other, |a, b| a ^ b);
+    }
+
+    pub fn not_assign(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = !*word);
+        self.clear_padding();
+    }
+
+    /// `row` shifted so that column `c` holds what was at column `c - 1`.
+    fn shifted_right(row: &[u64], out: &mut [u64]) {
+        let mut carry = 0;
+        for (word, out) in row.iter().zip(out.iter_mut()) {
+            *out = (word << 1) | carry;
+            carry = word >> (WORD_BITS - 1);
+        }
+    }
+
+    /// `row` shifted so that column `c` holds what was at column `c + 1`.
+    fn shifted_left(row: &[u64], out: &mut [u64]) {
+        let mut carry = 0;
+        for (word, out) in row.iter().zip(out.iter_mut()).rev() {
+            *out = (word >> 1) | carry;
+            carry = word << (WORD_BITS - 1);
+        }
+    }
+
+    /// Adds the 8 neighbors of every cell in `row` into `counters`, a
+    /// bit-sliced 4-bit counter per column: count = c0 + 2*c1 + 4*c2 + 8*c3.
+    /// `left` and `right` are scratch rows, reused to avoid allocating.
+    fn count_row_neighbors8(&self, row: usize, counters: &mut [Vec<u64>; 4], left: &mut [u64], right: &mut [u64]) {
+        counters.iter_mut().for_each(|layer| layer.fill(0));
+        let mut add = |layer: &[u64]| {
+            for idx in 0..self.row_words {
+                let mut carry = layer[idx];
+                for bit in counters.iter_mut() {
+                    let next = bit[idx] & carry;
+                    bit[idx] ^= carry;
+                    carry = next;
+                }
+            }
+        };
+
+        let rows = [row.checked_sub(1), Some(row), Some(row + 1).filter(|r| *r < self.rows)];
+        for (idx, other) in rows.into_iter().enumerate() {
+            let Some(other) = other else { continue };
+            let words = self.row_words(other);
+            Self::shifted_left(words, left);
+            Self::shifted_right(words, right);
+            add(left);
+            add(right);
+            if idx != 1 {
+                add(words);
+            }
+        }
+    }
+
+    /// Word with a bit set for every column whose counter equals `count`.
+    fn counter_mask(counters: &[Vec<u64>; 4], idx: usize, count: u8) -> u64 {
+        (0..4).fold(!0, |mask, layer| {
+            let word = counters[layer][idx];
+            mask & if count >> layer & 1 == 1 { word } else { !word }
+        })
+    }
+
+    /// Number of set cells among the 8 neighbors of every cell.
+    ///
+    /// Adds the shifted rows above, below and beside each row as bit-sliced
+    /// counters, 64 cells per word operation.
+    pub fn neighbor_counts8(&self) -> Matrix<u8> {
+        let mut counts = Matrix::new(self.rows, self.cols);
+        let mut counters = [(); 4].map(|_| vec![0; self.row_words]);
+        let mut left = vec![0; self.row_words];
+        let mut right = vec![0; self.row_words];
+
+        for row in 0..self.rows {
+            self.count_row_neighbors8(row, &mut counters, &mut left, &mut right);
+            for (col, count) in counts.row_mut(row).iter_mut().enumerate() {
+                let (word, bit) = (col / WORD_BITS, col % WORD_BITS);
+                *count = (0..4).map(|layer| (((counters[layer][word] >> bit) & 1) as u8) << layer).sum();
+            }
+        }
+
+        counts
+    }
+
+    /// Replaces every cell by `rule(alive, neighbor_count)`, as in a
+    /// life-like automaton. Returns how many cells changed.
+    ///
+    /// `rule` is only called once per state and count, the grid is then
+    /// updated 64 cells at a time from the bit-sliced counters.
+    pub fn step8(&mut self, rule: impl Fn(bool, u8) -> bool) -> usize {
+        let counts: Vec<u8> = (0..=8).collect();
+        let born: Vec<u8> = counts.iter().copied().filter(|count| rule(false, *count)).collect();
+        let survive: Vec<u8> = counts.iter().copied().filter(|count| rule(true, *count)).collect();
+
+        let mut counters = [(); 4].map(|_| vec![0; self.row_words]);
+        let mut left = vec![0; self.row_words];
+        let mut right = vec![0; self.row_words];
+        let mut next = vec![0; self.words.len()];
+
+        for row in 0..self.rows {
+            self.count_row_neighbors8(row, &mut counters, &mut left, &mut right);
+            let start = row * self.row_words;
+            for idx in 0..self.row_words {
+                let matching = |counts: &[u8]| {
+                    counts.iter().fold(0, |mask, count| mask | Self::counter_mask(&counters, idx, *count))
+                };
+                let alive = self.words[start + idx];
+                next[start + idx] = (alive & matching(&survive)) | (!alive & matching(&born));
+            }
+        }
+
+        std::mem::swap(&mut self.words, &mut next);
+        self.clear_padding();
+        next.iter().zip(&self.words).map(|(a, b)| (a ^ b).count_ones() as usize).sum()
+    }
+
+    /// Set cells 4-connected to `start` through set cells, grown a whole
+    /// BFS layer per pass with word-wide shifts.
+    pub fn reachable4(&self, start: Pos) -> BitMatrix {
+        let mut reached = BitMatrix::new(self.rows, self.cols);
+        if !self.get(start) {
+            return reached;
+        }
+        reached.set(start, true);
+
+        let mut shifted = vec![0; self.row_words];
+        loop {
+            let mut next = reached.clone();
+            for row in 0..self.rows {
+                Self::shifted_left(reached.row_words(row), &mut shifted);
+                next.or_row_assign(row, &shifted);
+                Self::shifted_right(reached.row_words(row), &mut shifted);
+                next.or_row_assign(row, &shifted);
+
+                for other in [row.checked_sub(1), Some(row + 1).filter(|r| *r < self.rows)].into_iter().flatten() {
+                    next.or_row_assign(row, reached.row_words(other));
+                }
+            }
+            next.and_assign(self);
+
+            if next == reached {
+                return reached;
+            }
+            reached = next;
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix<bool> {
+        let mut matrix = Matrix::new(self.rows, self.cols);
+        for pos in self.iter_ones() {
+            matrix[pos] = true;
+        }
+        matrix
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Packs the cells matching `pred`, like `'@'` in a `Matrix<char>`.
+    pub fn to_bits(&self, pred: impl Fn(&T) -> bool) -> BitMatrix {
+        let mut bits = BitMatrix::new(self.rows, self.cols);
+        for (pos, val) in self.iter_pos() {
+            if pred(val) {
+                bits.set(pos, true);
+            }
+        }
+        bits
+    }
+}
+
+impl From<&Matrix<bool>> for BitMatrix {
+    fn from(value: &Matrix<bool>) -> Self {
+        value.to_bits(|val| *val)
+    }
+}
+
+impl Display for BitMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                write!(f, "{}", if self.get(Pos(row as i32, col as i32)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_basics() {
+        let grid: Matrix<char> = "@.@\n.@.".try_into().unwrap();
+        let mut bits = grid.to_bits(|c| *c == '@');
+
+        assert_eq!(bits.count_ones(), 3);
+        assert!(bits.get(Pos(1, 1)));
+        assert!(!bits.get(Pos(-1, 0)));
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), [Pos(0, 0), Pos(0, 2), Pos(1, 1)]);
+        assert_eq!(bits.to_string(), "#.#\n.#.\n");
+
+        let mut other = BitMatrix::new(2, 3);
+        other.set(Pos(0, 0), true);
+        other.set(Pos(1, 0), true);
+
+        let mut and = bits.clone();
+        and.and_assign(&other);
+        assert_eq!(and.count_ones(), 1);
+
+        let mut xor = bits.clone();
+        xor.xor_assign(&other);
+        assert_eq!(xor.count_ones(), 3);
+
+        let mut rows = bits.clone();
+        rows.xor_row_assign(1, &[!0]);
+        assert_eq!(rows.to_string(), "#.#\n#.#\n");
+        rows.and_row_assign(0, other.row_words(0));
+        rows.or_row_assign(1, &[0b010]);
+        assert_eq!(rows.to_string(), "#..\n###\n");
+        rows.row_words_mut(0)[0] = 0b110;
+        assert_eq!(rows.count_row_ones(0), 2);
+
+        bits.not_assign();
+        assert_eq!(bits.count_ones(), 3);
+        assert!(bits.to_matrix()[Pos(0, 1)]);
+    }
+
+    #[test]
+    fn test_step8_and_reachable() {
+        let grid: Matrix<char> = ".....\n..#..\n..#..\n..#..\n.....".try_into().unwrap();
+        let mut bits = grid.to_bits(|c| *c == '#');
+
+        let changes = bits.step8(|alive, count| count == 3 || (alive && count == 2));
+
+        assert_eq!(changes, 4);
+        assert_eq!(bits.to_string(), ".....\n.....\n.###.\n.....\n.....\n");
+
+        let maze: Matrix<char> = "..#..\n#.#..\n..#..\n.##..\n.....".try_into().unwrap();
+        let open = maze.to_bits(|c| *c == '.');
+        let reached = open.reachable4(Pos(0, 0));
+        assert_eq!(reached.count_ones(), open.count_ones());
+        assert!(open.reachable4(Pos(0, 2)).count_ones() == 0);
+    }
+
+    #[test]
+    fn test_neighbor_counts_across_words() {
+        let grid = Matrix::parse_with(
+            &["#".repeat(130), "#".repeat(130), ".".repeat(64) + "#" + &".".repeat(65)].join("\n"),
+            |c| Ok::<_, ()>(c == '#'),
+        ).unwrap();
+        let bits = BitMatrix::from(&grid);
+
+        let counts = bits.neighbor_counts8();
+
+        for (pos, count) in counts.iter_pos() {
+            assert_eq!(*count as usize, grid.count_neighbors8(pos, |b| *b), "{pos}");
+        }
+
+        // Birth on zero neighbors must not leak into the padding bits
+        let mut stepped = bits.clone();
+        let changes = stepped.step8(|alive, count| if alive { count >= 4 } else { count == 0 || count == 3 });
+        for (pos, count) in counts.iter_pos() {
+            let alive = bits.get(pos);
+            assert_eq!(stepped.get(pos), if alive { *count >= 4 } else { *count == 0 || *count == 3 }, "{pos}");
+        }
+        let expected = counts.iter_pos().filter(|(pos, _)| stepped.get(*pos) != bits.get(*pos)).count();
+        assert_eq!(changes, expected);
+        assert_eq!(stepped.count_ones(), stepped.iter_ones().count());
+        assert!(stepped.iter_ones().all(|pos| pos.1 < 130));
+    }
+}
@@ -0,0 +1,162 @@
+use std::{collections::HashSet, fmt::{self, Display}};
+
+use super::{Matrix, MatrixView, Pos, Rect};
+
+/// How [`Matrix::display_with`] lays out cells. The default separates
+/// cells with a single space, like `Display` for `Matrix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayOptions<'a> {
+    pub separator: &'a str,
+    /// Right-aligns every cell to this many characters.
+    pub width: Option<usize>,
+    /// Adds column indices on top and row indices on the left. Columns show
+    /// their last digit when the cell is too narrow for the whole index.
+    /// Without a `width`, cells are aligned to the widest one.
+    pub rulers: bool,
+    pub highlights: &'a [Pos],
+    /// ANSI SGR code, such as 31 for red or 7 for inverted.
+    pub highlight_color: u8,
+    pub path: &'a [Pos],
+    pub path_color: u8,
+}
+
+impl Default for DisplayOptions<'_> {
+    fn default() -> Self {
+        Self {
+            separator: " ",
+            width: None,
+            rulers: false,
+            highlights: &[],
+            highlight_color: 31,
+            path: &[],
+            path_color: 32,
+        }
+    }
+}
+
+impl DisplayOptions<'_> {
+    /// Cells back to back, as in puzzle inputs.
+    pub fn compact() -> Self {
+        Self { separator: "", ..Default::default() }
+    }
+}
+
+pub struct MatrixDisplay<'a, T> {
+    view: MatrixView<'a, T>,
+    options: DisplayOptions<'a>,
+}
+
+impl<T> Matrix<T> {
+    pub fn display_with<'a>(&'a self, options: DisplayOptions<'a>) -> MatrixDisplay<'a, T> {
+        let view = self.view(Rect::new(Pos(0, 0), self.rows, self.cols)).expect("Matrix should fit in its own bounds");
+        view.display_with(options)
+    }
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    /// Like [`Matrix::display_with`], with positions local to the view.
+    pub fn display_with(&self, options: DisplayOptions<'a>) -> MatrixDisplay<'a, T> {
+        MatrixDisplay { view: *self, options }
+    }
+}
+
+impl Matrix<char> {
+    /// The grid as puzzle text, one line per row. Parses back into the same
+    /// matrix with `try_into`.
+    pub fn to_puzzle_string(&self) -> String {
+        self.display_with(DisplayOptions::compact()).to_string()
+    }
+}
+
+fn digits(num: usize) -> usize {
+    num.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+impl<T: Display> Display for MatrixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DisplayOptions { separator, mut width, rulers, .. } = self.options;
+        let view = self.view;
+        if rulers && width.is_none() {
+            width = view.iter().map(|item| item.to_string().chars().count()).max();
+        }
+        let cell_width = width.unwrap_or(1).max(1);
+        let label_width = digits(view.rows().saturating_sub(1));
+
+        let highlights: HashSet<&Pos> = self.options.highlights.iter().collect();
+        let path: HashSet<&Pos> = self.options.path.iter().collect();
+
+        if rulers {
+            write!(f, "{:label_width$} ", "")?;
+            for col in 0..view.cols() {
+                if col != 0 { write!(f, "{separator}")?; }
+                let label = if digits(col) <= cell_width { col } else { col % 10 };
+                write!(f, "{label:>cell_width$}")?;
+            }
+            writeln!(f)?;
+        }
+
+        for row in 0..view.rows() {
+            if rulers { write!(f, "{row:>label_width$} ")?; }
+
+            for (col, item) in view.row(row).iter().enumerate() {
+                if col != 0 { write!(f, "{separator}")?; }
+
+                let pos = Pos(row as i32, col as i32);
+                let color = if highlights.contains(&pos) {
+                    Some(self.options.highlight_color)
+                } else if path.contains(&pos) {
+                    Some(self.options.path_color)
+                } else {
+                    None
+                };
+
+                if let Some(color) = color { write!(f, "\x1b[{color}m")?; }
+                match width {
+                    Some(width) => write!(f, "{item:>width$}")?,
+                    None => write!(f, "{item}")?,
+                }
+                if color.is_some() { write!(f, "\x1b[0m")?; }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_round_trip() {
+        let text = "#.é\n..#\n";
+        let matrix: Matrix<char> = text.try_into().unwrap();
+
+        assert_eq!(matrix.to_puzzle_string(), text);
+
+        let back: Matrix<char> = matrix.to_puzzle_string().try_into().unwrap();
+        assert!(back.iter().eq(matrix.iter()));
+    }
+
+    #[test]
+    fn test_display_options() {
+        let matrix: Matrix<i32> = (vec![1, 20, 300, 4, 5, 6], 3).try_into().unwrap();
+
+        assert_eq!(matrix.to_string(), "1 20 300\n4 5 6\n");
+
+        let options = DisplayOptions { width: Some(3), rulers: true, ..Default::default() };
+        assert_eq!(matrix.display_with(options).to_string(), "    0   1   2\n0   1  20 300\n1   4   5   6\n");
+
+        let options = DisplayOptions { highlights: &[Pos(1, 0)], path: &[Pos(0, 2)], ..DisplayOptions::compact() };
+        assert_eq!(matrix.display_with(options).to_string(), "120\x1b[32m300\x1b[0m\n\x1b[31m4\x1b[0m56\n");
+
+        // Rulers without a width line up with the widest cell
+        let options = DisplayOptions { rulers: true, ..Default::default() };
+        assert_eq!(matrix.display_with(options).to_string(), "    0   1   2\n0   1  20 300\n1   4   5   6\n");
+
+        let view = matrix.view(Rect::new(Pos(0, 1), 2, 2)).unwrap();
+        let options = DisplayOptions { highlights: &[Pos(0, 0)], ..DisplayOptions::compact() };
+        assert_eq!(view.display_with(options).to_string(), "\x1b[31m20\x1b[0m300\n56\n");
+    }
+}
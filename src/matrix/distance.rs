@@ -0,0 +1,163 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::{Matrix, Pos};
+use crate::search;
+
+/// Which source a cell is closest to, see [`Matrix::voronoi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Owner {
+    /// Index of the source in the slice given.
+    Seed(usize),
+    /// Two or more sources are equally close.
+    Tie,
+}
+
+impl<T> Matrix<T> {
+    fn costs_to_matrix(&self, costs: impl IntoIterator<Item = (Pos, u32)>) -> Matrix<Option<u32>> {
+        let mut dists = Matrix::new(self.rows, self.cols);
+        for (pos, cost) in costs {
+            dists[pos] = Some(cost);
+        }
+        dists
+    }
+
+    /// Steps from every cell to the nearest of `sources`, moving between
+    /// 4-neighbors that are `passable`. Sources outside the matrix are
+    /// ignored and unreachable cells are `None`.
+    pub fn distance_map(&self, sources: impl IntoIterator<Item = Pos>, passable: impl Fn(&T) -> bool) -> Matrix<Option<u32>> {
+        let search = search::bfs(
+            sources.into_iter().filter(|pos| self.get(*pos).is_some()),
+            |pos| self.neighbors4(*pos).filter(|(_, val)| passable(val)).map(|(next, _)| next).collect::<Vec<_>>(),
+            |_| false,
+        );
+
+        self.costs_to_matrix(search.costs().map(|(pos, cost)| (*pos, cost as u32)))
+    }
+
+    /// Like [`Matrix::distance_map`], where entering a cell costs
+    /// `step_cost` of its value, `None` for walls.
+    pub fn weighted_distance_map(
+        &self,
+        sources: impl IntoIterator<Item = Pos>,
+        step_cost: impl Fn(&T) -> Option<u32>,
+    ) -> Matrix<Option<u32>> {
+        let search = search::dijkstra(
+            sources.into_iter().filter(|pos| self.get(*pos).is_some()),
+            |pos| {
+                self.neighbors4(*pos)
+                    .filter_map(|(next, val)| step_cost(val).map(|cost| (next, cost)))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        );
+
+        self.costs_to_matrix(search.costs().map(|(pos, cost)| (*pos, cost)))
+    }
+
+    /// Splits the grid by nearest source, measured as the cheapest path
+    /// through 4-neighbors where entering a cell costs `metric` of its value
+    /// (`None` for walls). Use `|_| Some(1)` for plain steps. Costs should be
+    /// positive for ties to be exact. Unreachable cells are `None`.
+    pub fn voronoi(&self, sources: &[Pos], metric: impl Fn(&T) -> Option<u32>) -> Matrix<Option<Owner>> {
+        let mut dists: Matrix<Option<u32>> = Matrix::new(self.rows, self.cols);
+        let mut owners: Matrix<Option<Owner>> = Matrix::new(self.rows, self.cols);
+        let mut heap = BinaryHeap::new();
+
+        for (idx, source) in sources.iter().enumerate() {
+            if self.get(*source).is_none() { continue; }
+
+            owners[*source] = match owners[*source] {
+                None => Some(Owner::Seed(idx)),
+                Some(_) => Some(Owner::Tie),
+            };
+            if dists[*source].is_none() {
+                dists[*source] = Some(0);
+                heap.push(Reverse((0, *source)));
+            }
+        }
+
+        while let Some(Reverse((dist, pos))) = heap.pop() {
+            if dists[pos] != Some(dist) { continue; }
+
+            let owner = owners[pos];
+            for (next, val) in self.neighbors4(pos) {
+                let Some(cost) = metric(val) else { continue };
+                let next_dist = dist + cost;
+
+                match dists[next] {
+                    Some(old) if next_dist > old => {},
+                    Some(old) if next_dist == old => {
+                        if owners[next] != owner {
+                            owners[next] = Some(Owner::Tie);
+                        }
+                    },
+                    _ => {
+                        dists[next] = Some(next_dist);
+                        owners[next] = owner;
+                        heap.push(Reverse((next_dist, next)));
+                    },
+                }
+            }
+        }
+
+        owners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_map() {
+        let grid: Matrix<char> = "\
+S..#.
+.#.#.
+...#S".try_into().unwrap();
+        let sources: Vec<Pos> = grid.positions(|c| *c == 'S').collect();
+
+        let dists = grid.distance_map(sources.iter().copied(), |c| *c != '#');
+
+        assert_eq!(dists[Pos(0, 0)], Some(0));
+        assert_eq!(dists[Pos(2, 2)], Some(4));
+        assert_eq!(dists[Pos(0, 4)], Some(2));
+        assert_eq!(dists[Pos(0, 3)], None);
+        assert_eq!(dists[Pos(1, 1)], None);
+
+        // Crossing a '~' costs 5 instead of 1
+        let swamp: Matrix<char> = "S~.\n...".try_into().unwrap();
+        let dists = swamp.weighted_distance_map([Pos(0, 0)], |c| match c {
+            '~' => Some(5),
+            _ => Some(1),
+        });
+        assert_eq!(dists[Pos(0, 2)], Some(4));
+        assert_eq!(dists[Pos(0, 1)], Some(5));
+    }
+
+    #[test]
+    fn test_voronoi() {
+        let grid: Matrix<char> = "\
+A....
+.....
+#####
+....B".try_into().unwrap();
+        let sources = [Pos(0, 0), Pos(3, 4)];
+
+        let owners = grid.voronoi(&sources, |c| (*c != '#').then_some(1));
+
+        assert_eq!(owners[Pos(1, 4)], Some(Owner::Seed(0)));
+        assert_eq!(owners[Pos(3, 0)], Some(Owner::Seed(1)));
+        assert_eq!(owners[Pos(2, 2)], None);
+
+        let open: Matrix<char> = ".....".try_into().unwrap();
+        let owners = open.voronoi(&[Pos(0, 0), Pos(0, 4)], |_| Some(1));
+        assert_eq!(owners.into_iter().collect::<Vec<_>>(), [
+            Some(Owner::Seed(0)), Some(Owner::Seed(0)), Some(Owner::Tie), Some(Owner::Seed(1)), Some(Owner::Seed(1)),
+        ]);
+
+        // The tie spreads to cells only reachable through it
+        let open: Matrix<char> = "...\n...".try_into().unwrap();
+        let owners = open.voronoi(&[Pos(0, 0), Pos(0, 2)], |_| Some(1));
+        assert_eq!(owners[Pos(1, 1)], Some(Owner::Tie));
+    }
+}
@@ -0,0 +1,304 @@
+use std::{collections::{hash_map, HashMap, HashSet}, fmt::Display, ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign}};
+
+use crate::search;
+
+/// Axial hex coordinate. The implied third cube coordinate is `s = -q - r`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex(pub i32, pub i32);
+
+impl Hex {
+    /// Clockwise from east, for pointy-top hexes.
+    pub const DIRECTIONS: [Hex; 6] = [Hex(1, 0), Hex(0, 1), Hex(-1, 1), Hex(-1, 0), Hex(0, -1), Hex(1, -1)];
+
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates should add up to 0");
+        Hex(q, r)
+    }
+
+    pub fn q(&self) -> i32 {
+        self.0
+    }
+
+    pub fn r(&self) -> i32 {
+        self.1
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.0 - self.1
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        Self::DIRECTIONS.into_iter().map(move |dir| self + dir)
+    }
+
+    /// Number of steps between the two hexes.
+    pub fn distance(&self, other: &Hex) -> i64 {
+        // Differences in i64, as far apart hexes overflow an i32
+        let dq = self.0 as i64 - other.0 as i64;
+        let dr = self.1 as i64 - other.1 as i64;
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+
+    /// Rotates by `steps` sixths of a turn clockwise around `center`.
+    /// Negative steps turn counter-clockwise.
+    pub fn rotate_around(self, center: Hex, steps: i32) -> Hex {
+        let mut rel = self - center;
+        for _ in 0..steps.rem_euclid(6) {
+            rel = Hex(-rel.r(), -rel.s());
+        }
+        rel + center
+    }
+
+    /// Hexes exactly `radius` steps from `center`, walking clockwise.
+    pub fn ring(center: Hex, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![center];
+        }
+
+        let mut hex = center + Self::DIRECTIONS[4] * radius as i32;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for dir in Self::DIRECTIONS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += dir;
+            }
+        }
+        ring
+    }
+
+    /// `center` then each ring out to `radius`, inclusive.
+    pub fn spiral(center: Hex, radius: u32) -> Vec<Hex> {
+        (0..=radius).flat_map(|radius| Self::ring(center, radius)).collect()
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
+    }
+}
+
+impl Add<Hex> for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl AddAssign<Hex> for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl Sub<Hex> for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign<Hex> for Hex {
+    fn sub_assign(&mut self, rhs: Hex) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Hex(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex(-self.0, -self.1)
+    }
+}
+
+/// Map of hex cells of any shape. Hexes without a value are outside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexMap<T> {
+    cells: HashMap<Hex, T>,
+}
+
+impl<T> Default for HexMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HexMap<T> {
+    pub fn new() -> Self {
+        Self { cells: HashMap::new() }
+    }
+
+    /// Hexagon-shaped map of every hex within `radius` of the origin.
+    pub fn hexagon(radius: u32, def: T) -> Self where T: Clone {
+        Hex::spiral(Hex(0, 0), radius).into_iter().map(|hex| (hex, def.clone())).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.cells.get(&hex)
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.cells.get_mut(&hex)
+    }
+
+    pub fn insert(&mut self, hex: Hex, val: T) -> Option<T> {
+        self.cells.insert(hex, val)
+    }
+
+    pub fn remove(&mut self, hex: Hex) -> Option<T> {
+        self.cells.remove(&hex)
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Hex, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> hash_map::IterMut<'_, Hex, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Neighboring hexes that are part of the map.
+    pub fn neighbors(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbors().filter_map(|next| self.get(next).map(|val| (next, val)))
+    }
+
+    pub fn count_neighbors(&self, hex: Hex, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.neighbors(hex).filter(|(_, val)| pred(val)).count()
+    }
+
+    /// Hexes connected to `start` through cells matching `passable`.
+    pub fn flood_fill(&self, start: Hex, passable: impl Fn(&T) -> bool) -> HashSet<Hex> {
+        let mut filled = HashSet::new();
+        if !self.get(start).is_some_and(&passable) {
+            return filled;
+        }
+
+        filled.insert(start);
+        let mut stack = vec![start];
+        while let Some(hex) = stack.pop() {
+            for (next, val) in self.neighbors(hex) {
+                if passable(val) && filled.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// Same contract as [`Matrix::astar`](super::Matrix::astar): the path
+    /// starts at `start` and stops before `target`.
+    pub fn astar<C, I>(
+        &self,
+        start: Hex,
+        target: Hex,
+        mut move_options: impl FnMut(&Self, &Hex) -> I,
+        heuristic: impl Fn(&Hex, &Hex) -> C,
+    ) -> Option<(Vec<Hex>, C)>
+    where
+        C: Ord + Add<Output = C> + Default + Copy,
+        I: IntoIterator<Item = (C, Hex)>,
+    {
+        let search = search::astar(
+            [start],
+            |hex| move_options(self, hex).into_iter().map(|(cost, next)| (next, cost)),
+            |hex| heuristic(hex, &target),
+            |hex| *hex == target,
+        );
+
+        let cost = search.goal_cost()?;
+        let mut path = search.path(&target)?;
+        path.pop();
+        Some((path, cost))
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        Self { cells: iter.into_iter().collect() }
+    }
+}
+
+impl<T> Index<Hex> for HexMap<T> {
+    type Output = T;
+
+    fn index(&self, index: Hex) -> &Self::Output {
+        self.get(index).expect("Hex should be part of the map")
+    }
+}
+
+impl<T> IndexMut<Hex> for HexMap<T> {
+    fn index_mut(&mut self, index: Hex) -> &mut Self::Output {
+        self.get_mut(index).expect("Hex should be part of the map")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_geometry() {
+        let hex = Hex::from_cube(2, -1, -1);
+
+        assert_eq!(hex.s(), -1);
+        assert_eq!(hex.distance(&Hex(0, 0)), 2);
+        assert_eq!(Hex(-3, 1).distance(&Hex(2, -2)), 5);
+        assert_eq!(Hex(i32::MIN, 0).distance(&Hex(i32::MAX, 0)), u32::MAX as i64);
+        assert_eq!(Hex(i32::MIN, i32::MAX).distance(&Hex(i32::MAX, i32::MIN)), u32::MAX as i64);
+        assert_eq!(hex.rotate_around(Hex(0, 0), 1), Hex(1, 1));
+        assert_eq!(hex.rotate_around(Hex(0, 0), -1), Hex(1, -2));
+        assert_eq!(hex.rotate_around(Hex(1, 1), 6), hex);
+        assert!(hex.neighbors().all(|next| next.distance(&hex) == 1));
+
+        let ring = Hex::ring(Hex(1, -1), 2);
+        assert_eq!(ring.len(), 12);
+        assert!(ring.iter().all(|h| h.distance(&Hex(1, -1)) == 2));
+        assert!(ring.windows(2).all(|pair| pair[0].distance(&pair[1]) == 1));
+        assert_eq!(Hex::spiral(Hex(0, 0), 3).len(), 37);
+    }
+
+    #[test]
+    fn test_hex_map_search() {
+        let mut map = HexMap::hexagon(2, '.');
+        // Wall between the center and the east edge, open at the ends
+        for wall in [Hex(1, -1), Hex(1, 0), Hex(0, 1)] {
+            map[wall] = '#';
+        }
+
+        let (path, cost) = map.astar(
+            Hex(0, 0),
+            Hex(2, 0),
+            |map, hex| map.neighbors(*hex).filter(|(_, c)| **c != '#').map(|(hex, _)| (1, hex)).collect::<Vec<_>>(),
+            |from, to| from.distance(to),
+        ).unwrap();
+
+        // Around either end of the wall
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[0], Hex(0, 0));
+        assert_eq!(map.flood_fill(Hex(0, 0), |c| *c == '.').len(), 19 - 3);
+        assert_eq!(map.count_neighbors(Hex(0, 0), |c| *c == '#'), 3);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{fmt::Write as _, io::{self, Write}};
+
+use super::{Matrix, Pos};
+
+pub type Rgb = [u8; 3];
+
+/// Maps cell values to colors, falling back to `default` for values
+/// without an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette<T> {
+    pub entries: Vec<(T, Rgb)>,
+    pub default: Rgb,
+}
+
+impl<T: PartialEq> Palette<T> {
+    pub fn new(entries: Vec<(T, Rgb)>, default: Rgb) -> Self {
+        Self { entries, default }
+    }
+
+    pub fn color(&self, val: &T) -> Rgb {
+        self.entries.iter()
+            .find(|(key, _)| key == val)
+            .map_or(self.default, |(_, color)| *color)
+    }
+}
+
+/// Well separated colors for small ids, such as region labels.
+pub fn distinct_color(id: usize) -> Rgb {
+    const COLORS: [Rgb; 10] = [
+        [31, 119, 180], [255, 127, 14], [44, 160, 44], [214, 39, 40], [148, 103, 189],
+        [140, 86, 75], [227, 119, 194], [127, 127, 127], [188, 189, 34], [23, 190, 207],
+    ];
+    COLORS[id % COLORS.len()]
+}
+
+/// Extra layers drawn over the cells by [`Matrix::to_svg`]. Positions
+/// outside the matrix are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOptions<'a> {
+    pub cell_size: usize,
+    /// Drawn as a line through the cell centers, like an `astar` result.
+    pub path: &'a [Pos],
+    pub path_color: Rgb,
+    /// Outlined cells.
+    pub highlights: &'a [Pos],
+    pub highlight_color: Rgb,
+}
+
+impl Default for SvgOptions<'_> {
+    fn default() -> Self {
+        Self {
+            cell_size: 10,
+            path: &[],
+            path_color: [255, 0, 0],
+            highlights: &[],
+            highlight_color: [255, 215, 0],
+        }
+    }
+}
+
+fn hex_color(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+impl<T> Matrix<T> {
+    /// Rows of RGB bytes, each cell drawn as a `scale` x `scale` square.
+    fn pixels(&self, scale: usize, color: impl Fn(&T) -> Rgb) -> Vec<Vec<u8>> {
+        self.rows_iter()
+            .take(self.rows)
+            .flat_map(|row| {
+                let line: Vec<u8> = row.iter()
+                    .flat_map(|val| color(val).repeat(scale))
+                    .collect();
+                std::iter::repeat_n(line, scale)
+            })
+            .collect()
+    }
+
+    /// Binary PPM (P6) image.
+    pub fn write_ppm(&self, mut out: impl Write, scale: usize, color: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.cols * scale, self.rows * scale)?;
+        for line in self.pixels(scale, color) {
+            out.write_all(&line)?;
+        }
+
+        Ok(())
+    }
+
+    /// 8-bit RGB PNG, stored without compression.
+    pub fn write_png(&self, mut out: impl Write, scale: usize, color: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        let width = (self.cols * scale) as u32;
+        let height = (self.rows * scale) as u32;
+
+        let mut raw = Vec::with_capacity((width as usize * 3 + 1) * height as usize);
+        for line in self.pixels(scale, color) {
+            raw.push(0); // no filter
+            raw.extend(line);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(width.to_be_bytes());
+        header.extend(height.to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]); // 8 bits, RGB, deflate, no filter, no interlace
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(&mut out, b"IHDR", &header)?;
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut out, b"IEND", &[])
+    }
+
+    /// SVG with one rect per cell plus the overlays in `options`.
+    pub fn to_svg(&self, options: &SvgOptions, color: impl Fn(&T) -> Rgb) -> String {
+        let size = options.cell_size;
+        let inside = |pos: &Pos| pos.to_usize().filter(|(row, col)| *row < self.rows && *col < self.cols);
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" shape-rendering="crispEdges">"#,
+            self.cols * size,
+            self.rows * size,
+        );
+
+        for (row, cells) in self.rows_iter().take(self.rows).enumerate() {
+            for (col, val) in cells.iter().enumerate() {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="{}"/>"#,
+                    col * size,
+                    row * size,
+                    hex_color(color(val)),
+                );
+            }
+        }
+
+        for (row, col) in options.highlights.iter().filter_map(inside) {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                col * size,
+                row * size,
+                hex_color(options.highlight_color),
+                size.div_ceil(5),
+            );
+        }
+
+        let points: Vec<String> = options.path.iter()
+            .filter_map(inside)
+            .map(|(row, col)| format!("{},{}", col as f64 * size as f64 + size as f64 / 2., row as f64 * size as f64 + size as f64 / 2.))
+            .collect();
+        if !points.is_empty() {
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"/>"#,
+                points.join(" "),
+                hex_color(options.path_color),
+                size.div_ceil(3),
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        out.push(blocks.peek().is_none() as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Matrix<char> {
+        "#.\n.#".try_into().unwrap()
+    }
+
+    fn palette() -> Palette<char> {
+        Palette::new(vec![('#', [0, 0, 0])], [255, 255, 255])
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let palette = palette();
+        let mut out = Vec::new();
+
+        grid().write_ppm(&mut out, 2, |c| palette.color(c)).unwrap();
+
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(out.len(), header.len() + 4 * 4 * 3);
+        assert_eq!(&out[header.len()..header.len() + 9], &[0, 0, 0, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let palette = palette();
+        let mut out = Vec::new();
+
+        grid().write_png(&mut out, 1, |c| palette.color(c)).unwrap();
+
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&out[12..16], b"IHDR");
+        assert_eq!(&out[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+        // IEND chunk has a fixed CRC
+        assert_eq!(&out[out.len() - 8..], &[b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let palette = palette();
+        let options = SvgOptions {
+            path: &[Pos(0, 1), Pos(1, 1)],
+            highlights: &[Pos(0, 0)],
+            ..Default::default()
+        };
+
+        let svg = grid().to_svg(&options, |c| palette.color(c));
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.contains(r##"fill="#000000""##));
+        assert!(svg.contains(r#"points="15,5 15,15""#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        let options = SvgOptions {
+            path: &[Pos(-1, 0), Pos(0, 1), Pos(2, 0)],
+            highlights: &[Pos(0, -1), Pos(5, 5)],
+            ..Default::default()
+        };
+        let svg = grid().to_svg(&options, |c| palette.color(c));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(r#"points="15,5""#));
+    }
+}
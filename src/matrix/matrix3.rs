@@ -0,0 +1,258 @@
+use std::{fmt::Display, ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign}, slice::Iter};
+
+use super::Coord;
+
+/// Position as `(x, y, z)`, generic over the coordinate width like
+/// [`Pos`](super::Pos).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos3<N = i32>(pub N, pub N, pub N);
+
+impl Pos3 {
+    pub const NEIGHBORS6: [Pos3; 6] = [
+        Pos3(-1, 0, 0), Pos3(1, 0, 0),
+        Pos3(0, -1, 0), Pos3(0, 1, 0),
+        Pos3(0, 0, -1), Pos3(0, 0, 1),
+    ];
+
+    /// Every offset in the surrounding 3x3x3 cube except the center.
+    pub const NEIGHBORS26: [Pos3; 26] = {
+        let mut offsets = [Pos3(0, 0, 0); 26];
+        let mut idx = 0;
+        let mut cell = 0;
+        while cell < 27 {
+            if cell != 13 {
+                offsets[idx] = Pos3(cell / 9 - 1, cell / 3 % 3 - 1, cell % 3 - 1);
+                idx += 1;
+            }
+            cell += 1;
+        }
+        offsets
+    };
+}
+
+impl<N: Coord> Pos3<N> {
+    /// `(x, y, z)` as indices, `None` if any is negative or too large.
+    pub fn to_usize(self) -> Option<(usize, usize, usize)> {
+        Some((self.0.try_into().ok()?, self.1.try_into().ok()?, self.2.try_into().ok()?))
+    }
+
+    /// Summed in the wide type, see [`Pos::manhattan_distance`](super::Pos::manhattan_distance).
+    pub fn manhattan_distance(&self, other: &Pos3<N>) -> N::Wide {
+        self.0.wide_abs_diff(other.0) + self.1.wide_abs_diff(other.1) + self.2.wide_abs_diff(other.2)
+    }
+
+    /// Squared straight-line distance, exact and enough to compare by.
+    /// Always fits up to `i32` coordinates, `None` if it overflows `u128`.
+    pub fn squared_euclidean_distance(&self, other: &Pos3<N>) -> Option<u128> {
+        [(self.0, other.0), (self.1, other.1), (self.2, other.2)]
+            .into_iter()
+            .try_fold(0u128, |sum, (a, b)| {
+                let diff = a.to_i128().abs_diff(b.to_i128());
+                sum.checked_add(diff.checked_mul(diff)?)
+            })
+    }
+}
+
+impl<N: Display> Display for Pos3<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{},{})", self.0, self.1, self.2)
+    }
+}
+
+impl<N: Coord> Add<Pos3<N>> for Pos3<N> {
+    type Output = Pos3<N>;
+
+    fn add(self, rhs: Pos3<N>) -> Self::Output {
+        Pos3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl<N: Coord> AddAssign<Pos3<N>> for Pos3<N> {
+    fn add_assign(&mut self, rhs: Pos3<N>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+        self.2 += rhs.2;
+    }
+}
+
+impl<N: Coord> Sub<Pos3<N>> for Pos3<N> {
+    type Output = Pos3<N>;
+
+    fn sub(self, rhs: Pos3<N>) -> Self::Output {
+        Pos3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl<N: Coord> SubAssign<Pos3<N>> for Pos3<N> {
+    fn sub_assign(&mut self, rhs: Pos3<N>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+        self.2 -= rhs.2;
+    }
+}
+
+impl<N: Coord> Mul<N> for Pos3<N> {
+    type Output = Pos3<N>;
+
+    fn mul(self, rhs: N) -> Self::Output {
+        Pos3(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl<N: Coord> Neg for Pos3<N> {
+    type Output = Pos3<N>;
+
+    fn neg(self) -> Self::Output {
+        Pos3(-self.0, -self.1, -self.2)
+    }
+}
+
+/// Dense 3D grid indexed by `Pos3(x, y, z)` with `z` varying fastest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix3<T> {
+    vals: Vec<T>,
+    dims: (usize, usize, usize),
+}
+
+impl<T: Clone> Matrix3<T> {
+    pub fn with_default(dims: (usize, usize, usize), def: T) -> Self {
+        Self { vals: vec![def; dims.0 * dims.1 * dims.2], dims }
+    }
+}
+
+impl<T> Matrix3<T> {
+    pub fn dims(&self) -> (usize, usize, usize) {
+        self.dims
+    }
+
+    fn index_of<N: Coord>(&self, pos: Pos3<N>) -> Option<usize> {
+        let (x, y, z) = pos.to_usize()?;
+        let (dx, dy, dz) = self.dims;
+        if x >= dx || y >= dy || z >= dz {
+            return None;
+        }
+
+        Some((x * dy + y) * dz + z)
+    }
+
+    pub fn get<N: Coord>(&self, pos: Pos3<N>) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.vals[idx])
+    }
+
+    pub fn get_mut<N: Coord>(&mut self, pos: Pos3<N>) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.vals[idx])
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.vals.iter()
+    }
+
+    pub fn iter_pos(&self) -> impl Iterator<Item = (Pos3, &T)> {
+        let (_, dy, dz) = self.dims;
+        self.vals.iter().enumerate().map(move |(idx, val)| {
+            (Pos3((idx / (dy * dz)) as i32, (idx / dz % dy) as i32, (idx % dz) as i32), val)
+        })
+    }
+
+    pub fn neighbors_with<'a>(&'a self, pos: Pos3, offsets: &'a [Pos3]) -> impl Iterator<Item = (Pos3, &'a T)> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let next = pos + *offset;
+            self.get(next).map(|val| (next, val))
+        })
+    }
+
+    pub fn neighbors6(&self, pos: Pos3) -> impl Iterator<Item = (Pos3, &T)> {
+        self.neighbors_with(pos, &Pos3::NEIGHBORS6)
+    }
+
+    pub fn neighbors26(&self, pos: Pos3) -> impl Iterator<Item = (Pos3, &T)> {
+        self.neighbors_with(pos, &Pos3::NEIGHBORS26)
+    }
+
+    /// Cells 6-connected to `start` through cells matching `passable`.
+    pub fn flood_fill(&self, start: Pos3, passable: impl Fn(&T) -> bool) -> Matrix3<bool> {
+        let mut filled = Matrix3::with_default(self.dims, false);
+        if !self.get(start).is_some_and(&passable) {
+            return filled;
+        }
+
+        filled[start] = true;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for (next, val) in self.neighbors6(pos) {
+                if !filled[next] && passable(val) {
+                    filled[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        filled
+    }
+}
+
+impl<T, N: Coord> Index<Pos3<N>> for Matrix3<T> {
+    type Output = T;
+
+    fn index(&self, index: Pos3<N>) -> &Self::Output {
+        let idx = self.index_of(index).expect("Position should be inside the matrix");
+        &self.vals[idx]
+    }
+}
+
+impl<T, N: Coord> IndexMut<Pos3<N>> for Matrix3<T> {
+    fn index_mut(&mut self, index: Pos3<N>) -> &mut Self::Output {
+        let idx = self.index_of(index).expect("Position should be inside the matrix");
+        &mut self.vals[idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pos3() {
+        let a: Pos3 = Pos3(162, 817, 812);
+        let b = Pos3(425, 690, 689);
+
+        assert_eq!(a.squared_euclidean_distance(&b), Some(263 * 263 + 127 * 127 + 123 * 123));
+        assert_eq!(a.manhattan_distance(&b), 263 + 127 + 123);
+        assert_eq!(a - b + b, a);
+        assert_eq!(-Pos3(1, -2, 3) * 2, Pos3(-2, 4, -6));
+        assert_eq!(Pos3::NEIGHBORS26.len(), 26);
+        assert!(!Pos3::NEIGHBORS26.contains(&Pos3(0, 0, 0)));
+        assert!(Pos3::NEIGHBORS6.iter().all(|offset| Pos3::NEIGHBORS26.contains(offset)));
+
+        let (min, max) = (Pos3(i32::MIN, i32::MIN, i32::MIN), Pos3(i32::MAX, i32::MAX, i32::MAX));
+        let span = u32::MAX as u128;
+        assert_eq!(min.manhattan_distance(&max), 3 * u32::MAX as u64);
+        assert_eq!(min.squared_euclidean_distance(&max), Some(3 * span * span));
+
+        let (min, max) = (Pos3(i64::MIN, 0, 0), Pos3(i64::MAX, 0, 0));
+        assert_eq!(min.squared_euclidean_distance(&max), Some(u64::MAX as u128 * u64::MAX as u128));
+        assert_eq!(Pos3(i64::MIN, i64::MIN, 0).squared_euclidean_distance(&Pos3(i64::MAX, i64::MAX, 0)), None);
+        assert_eq!(Pos3(2i64, 1, 0) * 3, Pos3(6, 3, 0));
+    }
+
+    #[test]
+    fn test_matrix3_flood_fill() {
+        // Hollow 3x3x3 cube: the center can't reach the outside
+        let mut voxels = Matrix3::with_default((5, 5, 5), false);
+        for x in 1..4 {
+            for y in 1..4 {
+                for z in 1..4 {
+                    voxels[Pos3(x, y, z)] = (x, y, z) != (2, 2, 2);
+                }
+            }
+        }
+
+        let outside = voxels.flood_fill(Pos3(0, 0, 0), |solid| !solid);
+        let inside = voxels.flood_fill(Pos3(2, 2, 2), |solid| !solid);
+
+        assert_eq!(outside.iter().filter(|v| **v).count(), 125 - 27);
+        assert_eq!(inside.iter().filter(|v| **v).count(), 1);
+        assert_eq!(voxels.neighbors26(Pos3(0, 0, 0)).count(), 7);
+        assert_eq!(voxels.iter_pos().nth(7).map(|(pos, _)| pos), Some(Pos3(0, 1, 2)));
+    }
+}
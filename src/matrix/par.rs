@@ -0,0 +1,45 @@
+use rayon::prelude::*;
+
+use super::{Matrix, Pos};
+
+impl<T: Sync> Matrix<T> {
+    pub fn par_rows(&self) -> rayon::slice::Chunks<'_, T> {
+        self.vals.par_chunks(self.cols.max(1))
+    }
+
+    /// Parallel [`Matrix::iter_pos`].
+    pub fn par_iter_pos(&self) -> impl IndexedParallelIterator<Item = (Pos, &T)> {
+        let cols = self.cols;
+        self.vals.par_iter()
+            .enumerate()
+            .map(move |(idx, val)| (Pos((idx / cols) as i32, (idx % cols) as i32), val))
+    }
+
+    /// Matrix of the same shape with every cell computed by `op`, in
+    /// parallel.
+    pub fn par_map<U: Send>(&self, op: impl Fn(Pos, &T) -> U + Sync) -> Matrix<U> {
+        Matrix {
+            vals: self.par_iter_pos().map(|(pos, val)| op(pos, val)).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_matches_sequential() {
+        let grid: Matrix<char> = "..@@.\n@@@.@\n.@@@@\n@.@.@".try_into().unwrap();
+
+        let counts = grid.par_map(|pos, _| grid.count_neighbors8(pos, |c| *c == '@'));
+
+        for (pos, count) in counts.iter_pos() {
+            assert_eq!(*count, grid.count_neighbors8(pos, |c| *c == '@'));
+        }
+        assert!(grid.par_iter_pos().map(|(pos, _)| pos).collect::<Vec<_>>().into_iter().eq(grid.iter_pos().map(|(pos, _)| pos)));
+        assert_eq!(grid.par_rows().map(|row| row.iter().filter(|c| **c == '@').count()).collect::<Vec<_>>(), [2, 4, 4, 3]);
+    }
+}
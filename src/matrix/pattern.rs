@@ -0,0 +1,92 @@
+use super::{Matrix, Pos, Transform};
+
+impl<T> Matrix<T> {
+    /// First position in row-major order whose value matches `pred`.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter_pos().find(|(_, val)| pred(val)).map(|(pos, _)| pos)
+    }
+
+    /// Every position whose value matches `pred`, in row-major order.
+    pub fn positions<'a>(&'a self, mut pred: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = Pos> + 'a {
+        self.iter_pos().filter(move |(_, val)| pred(val)).map(|(pos, _)| pos)
+    }
+}
+
+impl<T: PartialEq> Matrix<T> {
+    fn matches_at(&self, pattern: &Matrix<Option<T>>, origin: Pos) -> bool {
+        pattern.iter_pos().all(|(offset, cell)| match cell {
+            Some(val) => self[origin + offset] == *val,
+            None => true,
+        })
+    }
+
+    /// Top-left corners where `pattern` fits, with `None` cells matching
+    /// anything.
+    pub fn find_all_patterns(&self, pattern: &Matrix<Option<T>>) -> Vec<Pos> {
+        if pattern.rows > self.rows || pattern.cols > self.cols {
+            return Vec::new();
+        }
+
+        Pos::iter(self.rows - pattern.rows + 1, self.cols - pattern.cols + 1)
+            .filter(|origin| self.matches_at(pattern, *origin))
+            .collect()
+    }
+
+    /// Like [`Matrix::find_all_patterns`], trying every rotation and
+    /// reflection of `pattern`. Each match comes with the transform applied
+    /// to the pattern. Symmetric patterns are only tried once per distinct
+    /// orientation, under the first transform in [`Transform::ALL`] giving it.
+    pub fn find_all_patterns_oriented(&self, pattern: &Matrix<Option<T>>) -> Vec<(Pos, Transform)>
+    where
+        T: Clone,
+    {
+        let mut tried: Vec<Matrix<Option<T>>> = Vec::new();
+        let mut found = Vec::new();
+        for transform in Transform::ALL {
+            let oriented = pattern.transform(transform);
+            let seen = tried.iter().any(|other| {
+                (other.rows, other.cols) == (oriented.rows, oriented.cols) && other.iter().eq(oriented.iter())
+            });
+            if seen { continue; }
+
+            found.extend(self.find_all_patterns(&oriented).into_iter().map(|pos| (pos, transform)));
+            tried.push(oriented);
+        }
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(text: &str) -> Matrix<Option<char>> {
+        Matrix::parse_with(text, |c| Ok::<_, ()>((c != '?').then_some(c))).unwrap()
+    }
+
+    #[test]
+    fn test_position() {
+        let grid: Matrix<char> = "...\n.S.\n..S".try_into().unwrap();
+
+        assert_eq!(grid.position(|c| *c == 'S'), Some(Pos(1, 1)));
+        assert_eq!(grid.positions(|c| *c == 'S').collect::<Vec<_>>(), [Pos(1, 1), Pos(2, 2)]);
+        assert_eq!(grid.position(|c| *c == 'X'), None);
+    }
+
+    #[test]
+    fn test_find_all_patterns() {
+        let grid: Matrix<char> = "#..#\n##.#\n..##".try_into().unwrap();
+
+        assert_eq!(grid.find_all_patterns(&pattern("?#\n?#")), [Pos(0, 2), Pos(1, 2)]);
+        assert!(grid.find_all_patterns(&pattern("#####")).is_empty());
+
+        let found = grid.find_all_patterns_oriented(&pattern("#.\n##"));
+        assert!(found.contains(&(Pos(0, 0), Transform::Identity)));
+        // ".#\n##" in the bottom-right corner is the pattern turned a quarter
+        // counter-clockwise, or mirrored, which is the same shape
+        assert!(found.contains(&(Pos(1, 2), Transform::RotateCcw)));
+        assert!(!found.iter().any(|(_, transform)| *transform == Transform::FlipHorizontal));
+        assert!(found.iter().all(|(pos, transform)| grid.matches_at(&pattern("#.\n##").transform(*transform), *pos)));
+    }
+}
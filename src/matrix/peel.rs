@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use super::{Connectivity, Matrix, Pos};
+
+impl<T> Matrix<T> {
+    /// Repeatedly removes every member cell with fewer than `threshold`
+    /// member neighbors, all at once per round, until none is left to
+    /// remove. Only cells next to a removal are ever re-checked.
+    ///
+    /// Returns how many cells were removed and the round (from 1) in which
+    /// each was, `None` for non-members and survivors.
+    pub fn peel(
+        &self,
+        connectivity: Connectivity,
+        threshold: usize,
+        is_member: impl Fn(&T) -> bool,
+    ) -> (usize, Matrix<Option<u32>>) {
+        let offsets = connectivity.offsets();
+        let mut rounds: Matrix<Option<u32>> = Matrix::new(self.rows, self.cols);
+        let mut counts = Matrix::with_default(self.rows, self.cols, 0);
+        let mut present = Matrix::with_default(self.rows, self.cols, false);
+        let mut queue = VecDeque::new();
+
+        for (pos, val) in self.iter_pos() {
+            if !is_member(val) { continue; }
+
+            present[pos] = true;
+            counts[pos] = self.count_neighbors_with(pos, offsets, &is_member);
+            if counts[pos] < threshold {
+                rounds[pos] = Some(1);
+                queue.push_back(pos);
+            }
+        }
+
+        let mut removed = 0;
+        while let Some(pos) = queue.pop_front() {
+            let round = rounds[pos].expect("Queued cells should have a round");
+            present[pos] = false;
+            removed += 1;
+
+            for offset in offsets {
+                let next: Pos = pos + *offset;
+                if present.get(next) != Some(&true) || rounds[next].is_some() { continue; }
+
+                counts[next] -= 1;
+                if counts[next] < threshold {
+                    rounds[next] = Some(round + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (removed, rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peel_rounds() {
+        let grid: Matrix<char> = "#####.##\n......##".try_into().unwrap();
+
+        let (removed, rounds) = grid.peel(Connectivity::Four, 2, |c| *c == '#');
+
+        assert_eq!(removed, 5);
+        assert_eq!(rounds.row(0), &[Some(1), Some(2), Some(3), Some(2), Some(1), None, None, None]);
+        assert!(rounds.row(1).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_peel_same_as_automaton() {
+        let grid: Matrix<char> = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.".try_into().unwrap();
+
+        let (removed, rounds) = grid.peel(Connectivity::Eight, 4, |c| *c == '@');
+
+        let mut automaton = grid.clone();
+        let mut round = 0;
+        loop {
+            let before = automaton.clone();
+            round += 1;
+            let changes = automaton.step(|cell| {
+                if *cell.cell() == '@' && cell.count8(|c| *c == '@') < 4 { '.' } else { *cell.cell() }
+            });
+            if changes == 0 { break; }
+
+            for (pos, val) in automaton.iter_pos() {
+                if before[pos] != *val {
+                    assert_eq!(rounds[pos], Some(round), "{pos}");
+                }
+            }
+        }
+
+        assert_eq!(removed, 43);
+    }
+}
@@ -0,0 +1,105 @@
+use super::{Matrix, Pos};
+
+/// Cells from a start position in a fixed direction, see [`Matrix::ray`].
+pub struct Ray<'a, T> {
+    matrix: &'a Matrix<T>,
+    pos: Pos,
+    dir: Pos,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.pos + self.dir;
+        let val = self.matrix.get(next)?;
+        self.pos = next;
+        Some((next, val))
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Cells met stepping `dir` from `start`, not including `start`, up to
+    /// the edge of the matrix.
+    pub fn ray(&self, start: Pos, dir: Pos) -> Ray<'_, T> {
+        assert_ne!(dir, Pos(0, 0), "Ray direction should not be zero");
+        Ray { matrix: self, pos: start, dir }
+    }
+
+    /// Like [`Matrix::ray`], ending after the first cell matching `stop`.
+    pub fn ray_until<'a>(&'a self, start: Pos, dir: Pos, mut stop: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        let mut stopped = false;
+        self.ray(start, dir).take_while(move |(_, val)| {
+            let go_on = !stopped;
+            stopped = stopped || stop(val);
+            go_on
+        })
+    }
+
+    /// First cell in direction `dir` from `start` that matches `pred`.
+    pub fn first_hit(&self, start: Pos, dir: Pos, mut pred: impl FnMut(&T) -> bool) -> Option<(Pos, &T)> {
+        self.ray(start, dir).find(|(_, val)| pred(val))
+    }
+
+    /// Cells seen from `start` looking along each of `dirs`. Sight ends at
+    /// the first `opaque` cell, which is itself visible.
+    pub fn visible_from(&self, start: Pos, dirs: &[Pos], opaque: impl Fn(&T) -> bool) -> Vec<Pos> {
+        dirs.iter()
+            .flat_map(|dir| self.ray_until(start, *dir, &opaque).map(|(pos, _)| pos))
+            .collect()
+    }
+
+    /// Number of the 8 directions in which the first `opaque` cell seen from
+    /// `start` is a `target`, like counting the occupied seats in view.
+    pub fn count_visible8(&self, start: Pos, opaque: impl Fn(&T) -> bool, target: impl Fn(&T) -> bool) -> usize {
+        Pos::NEIGHBORS8.iter()
+            .filter_map(|dir| self.first_hit(start, *dir, &opaque))
+            .filter(|(_, val)| target(val))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray() {
+        let grid: Matrix<char> = "....\n.#..\n....".try_into().unwrap();
+
+        let cells: Vec<Pos> = grid.ray(Pos(0, 0), Pos(0, 1)).map(|(pos, _)| pos).collect();
+        assert_eq!(cells, [Pos(0, 1), Pos(0, 2), Pos(0, 3)]);
+
+        let cells: Vec<Pos> = grid.ray_until(Pos(1, 3), Pos(0, -1), |c| *c == '#').map(|(pos, _)| pos).collect();
+        assert_eq!(cells, [Pos(1, 2), Pos(1, 1)]);
+
+        assert_eq!(grid.first_hit(Pos(2, 0), Pos(-1, 1), |c| *c == '#'), Some((Pos(1, 1), &'#')));
+        assert_eq!(grid.first_hit(Pos(2, 0), Pos(0, 1), |c| *c == '#'), None);
+    }
+
+    #[test]
+    fn test_visibility() {
+        let seats: Matrix<char> = "\
+#.L.#
+.....
+L.L.#
+.....
+#.#.L".try_into().unwrap();
+
+        let seat = |c: &char| *c != '.';
+        let occupied = |c: &char| *c == '#';
+
+        // Every direction ends at a seat, empty above, to the left and at the
+        // bottom right
+        assert_eq!(seats.count_visible8(Pos(2, 2), seat, occupied), 5);
+        // The nearest seats seen from the corner are all empty
+        assert_eq!(seats.count_visible8(Pos(0, 0), seat, occupied), 0);
+
+        let visible = seats.visible_from(Pos(2, 2), &Pos::NEIGHBORS4, seat);
+        assert_eq!(visible.len(), 8);
+        assert!(visible.contains(&Pos(0, 2)) && visible.contains(&Pos(2, 1)));
+
+        let visible = seats.visible_from(Pos(0, 0), &[Pos(0, 1)], seat);
+        assert_eq!(visible, [Pos(0, 1), Pos(0, 2)]);
+    }
+}
@@ -0,0 +1,163 @@
+use super::{Matrix, Pos, Rect};
+
+pub type RegionId = usize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Cells touching by an edge.
+    #[default]
+    Four,
+    /// Cells touching by an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [Pos] {
+        match self {
+            Connectivity::Four => &Pos::NEIGHBORS4,
+            Connectivity::Eight => &Pos::NEIGHBORS8,
+        }
+    }
+}
+
+/// A connected group of cells found by [`Matrix::label_regions`].
+/// Perimeter and sides are measured along cell edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub id: RegionId,
+    pub cells: Vec<Pos>,
+    pub perimeter: usize,
+    pub sides: usize,
+    pub bounds: Rect,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Labels the 4-connected regions of cells where `same` holds between
+    /// neighbors.
+    pub fn label_regions(&self, same: impl Fn(&T, &T) -> bool) -> (Matrix<RegionId>, Vec<Region>) {
+        self.label_regions_with(Connectivity::Four, same)
+    }
+
+    /// Labels every cell with the id of its region, ids counting up from 0
+    /// in row-major order of each region's first cell.
+    pub fn label_regions_with(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> (Matrix<RegionId>, Vec<Region>) {
+        let mut labels: Matrix<Option<RegionId>> = Matrix::new(self.rows, self.cols);
+        let mut regions = Vec::new();
+
+        for start in Pos::iter(self.rows, self.cols) {
+            if labels[start].is_some() { continue; }
+
+            let id = regions.len();
+            labels[start] = Some(id);
+            let mut cells = Vec::new();
+            let mut stack = vec![start];
+            while let Some(pos) = stack.pop() {
+                cells.push(pos);
+                for (next, val) in self.neighbors_with(pos, connectivity.offsets()) {
+                    if labels[next].is_none() && same(&self[pos], val) {
+                        labels[next] = Some(id);
+                        stack.push(next);
+                    }
+                }
+            }
+            cells.sort();
+
+            regions.push(Region { id, cells, perimeter: 0, sides: 0, bounds: Rect::default() });
+        }
+
+        let labels = Matrix {
+            vals: labels.vals.into_iter().map(|id| id.expect("Every cell should be labeled")).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        };
+
+        for region in regions.iter_mut() {
+            let inside = |pos: Pos| labels.get(pos) == Some(&region.id);
+            let (mut min, mut max) = (region.cells[0], region.cells[0]);
+
+            for &pos in &region.cells {
+                min = Pos(min.0.min(pos.0), min.1.min(pos.1));
+                max = Pos(max.0.max(pos.0), max.1.max(pos.1));
+
+                region.perimeter += Pos::NEIGHBORS4.iter().filter(|offset| !inside(pos + **offset)).count();
+
+                // A polygon has as many sides as corners. Check each corner of
+                // the cell for an outer (both edges open) or inner corner.
+                for (idx, a) in Pos::NEIGHBORS4.iter().enumerate() {
+                    let b = Pos::NEIGHBORS4[(idx + 1) % 4];
+                    let (side_a, side_b, diagonal) = (inside(pos + *a), inside(pos + b), inside(pos + *a + b));
+                    if (!side_a && !side_b) || (side_a && side_b && !diagonal) {
+                        region.sides += 1;
+                    }
+                }
+            }
+
+            region.bounds = Rect::from_corners(min, max);
+        }
+
+        (labels, regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_regions() {
+        // 2024 day 12 example
+        let garden: Matrix<char> = "\
+AAAA
+BBCD
+BBCC
+EEEC".try_into().unwrap();
+
+        let (labels, regions) = garden.label_regions(|a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels[Pos(1, 2)], labels[Pos(3, 3)]);
+
+        let stats: Vec<(usize, usize, usize)> = regions.iter()
+            .map(|region| (region.area(), region.perimeter, region.sides))
+            .collect();
+        assert_eq!(stats, [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]);
+        assert_eq!(regions[2].bounds, Rect::new(Pos(1, 2), 3, 2));
+    }
+
+    #[test]
+    fn test_label_regions_inner_hole() {
+        let garden: Matrix<char> = "\
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO".try_into().unwrap();
+
+        let (_, regions) = garden.label_regions(|a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!((regions[0].area(), regions[0].perimeter, regions[0].sides), (21, 36, 20));
+    }
+
+    #[test]
+    fn test_label_regions_eight_connected() {
+        let grid: Matrix<char> = "#.\n.#".try_into().unwrap();
+
+        let (_, four) = grid.label_regions_with(Connectivity::Four, |a, b| a == b);
+        let (labels, eight) = grid.label_regions_with(Connectivity::Eight, |a, b| a == b);
+
+        assert_eq!(four.len(), 4);
+        assert_eq!(eight.len(), 2);
+        assert_eq!(labels[Pos(0, 0)], labels[Pos(1, 1)]);
+    }
+}
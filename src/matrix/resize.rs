@@ -0,0 +1,186 @@
+use super::{Matrix, Pos, Rect};
+
+impl<T> Matrix<T> {
+    /// Inserts `row` before row `idx`, shifting the rest down. A row that
+    /// doesn't fit an empty matrix replaces it, leaving a single row.
+    pub fn insert_row(&mut self, idx: usize, row: Vec<T>) {
+        assert!(idx <= self.rows, "Row index should be at most the row count");
+        if self.vals.is_empty() && row.len() != self.cols {
+            assert_eq!(idx, 0, "Row index should be 0 when resizing an empty matrix");
+            self.rows = 0;
+            self.cols = row.len();
+        }
+        assert_eq!(row.len(), self.cols, "Row should have one value per column");
+
+        let start = idx * self.cols;
+        self.vals.splice(start..start, row);
+        self.rows += 1;
+    }
+
+    /// Inserts `col` before column `idx`, shifting the rest right. A column
+    /// that doesn't fit an empty matrix replaces it, leaving a single column.
+    pub fn insert_col(&mut self, idx: usize, col: Vec<T>) {
+        assert!(idx <= self.cols, "Column index should be at most the column count");
+        if self.vals.is_empty() && col.len() != self.rows {
+            assert_eq!(idx, 0, "Column index should be 0 when resizing an empty matrix");
+            self.rows = col.len();
+            self.cols = 0;
+        }
+        assert_eq!(col.len(), self.rows, "Column should have one value per row");
+
+        let mut vals = Vec::with_capacity(self.vals.len() + self.rows);
+        let mut old = std::mem::take(&mut self.vals).into_iter();
+        for val in col {
+            vals.extend(old.by_ref().take(idx));
+            vals.push(val);
+            vals.extend(old.by_ref().take(self.cols - idx));
+        }
+        self.vals = vals;
+        self.cols += 1;
+    }
+
+    pub fn remove_row(&mut self, idx: usize) -> Vec<T> {
+        assert!(idx < self.rows, "Row index should be inside the matrix");
+        let start = idx * self.cols;
+        self.rows -= 1;
+        self.vals.drain(start..start + self.cols).collect()
+    }
+
+    pub fn remove_col(&mut self, idx: usize) -> Vec<T> {
+        assert!(idx < self.cols, "Column index should be inside the matrix");
+        let mut removed = Vec::with_capacity(self.rows);
+        let mut vals = Vec::with_capacity(self.vals.len() - self.rows);
+        for (pos, val) in Pos::iter(self.rows, self.cols).zip(std::mem::take(&mut self.vals)) {
+            if pos.1 as usize == idx {
+                removed.push(val);
+            } else {
+                vals.push(val);
+            }
+        }
+        self.vals = vals;
+        self.cols -= 1;
+        removed
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Copy with a border `n` cells wide of `fill` on every side.
+    pub fn pad(&self, n: usize, fill: T) -> Matrix<T> {
+        let cols = self.cols + 2 * n;
+        let mut vals = Vec::with_capacity((self.rows + 2 * n) * cols);
+        vals.extend(std::iter::repeat_n(fill.clone(), n * cols));
+        for row in self.rows_iter().take(self.rows) {
+            vals.extend(std::iter::repeat_n(fill.clone(), n));
+            vals.extend_from_slice(row);
+            vals.extend(std::iter::repeat_n(fill.clone(), n));
+        }
+        vals.extend(std::iter::repeat_n(fill, n * cols));
+
+        Matrix { vals, rows: self.rows + 2 * n, cols }
+    }
+
+    /// `self` with `other` to its right.
+    pub fn hconcat(&self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.rows, other.rows, "Matrices should have the same rows");
+        let mut vals = Vec::with_capacity(self.vals.len() + other.vals.len());
+        for row in 0..self.rows {
+            vals.extend_from_slice(self.row(row));
+            vals.extend_from_slice(other.row(row));
+        }
+
+        Matrix { vals, rows: self.rows, cols: self.cols + other.cols }
+    }
+
+    /// `self` with `other` below it.
+    pub fn vconcat(&self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.cols, other.cols, "Matrices should have the same columns");
+        let mut vals = self.vals.clone();
+        vals.extend_from_slice(&other.vals);
+
+        Matrix { vals, rows: self.rows + other.rows, cols: self.cols }
+    }
+
+    /// `tile_rows` x `tile_cols` copies of the matrix. Each cell is
+    /// `map(tile, value)`, with `tile` the position of its copy.
+    pub fn tile(&self, tile_rows: usize, tile_cols: usize, map: impl Fn(Pos, &T) -> T) -> Matrix<T> {
+        let rows = self.rows * tile_rows;
+        let cols = self.cols * tile_cols;
+        let vals = Pos::iter(rows, cols)
+            .map(|pos| {
+                let (row, col) = (pos.0 as usize, pos.1 as usize);
+                let tile = Pos((row / self.rows) as i32, (col / self.cols) as i32);
+                map(tile, &self.vals[row % self.rows * self.cols + col % self.cols])
+            })
+            .collect();
+
+        Matrix { vals, rows, cols }
+    }
+
+    /// Copy of the cells in `rect`, or `None` if it doesn't fit.
+    pub fn crop(&self, rect: Rect) -> Option<Matrix<T>> {
+        self.view(rect).map(|view| view.to_matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Matrix<u32> {
+        (vec![1, 2, 3, 4, 5, 6], 3).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut matrix = grid();
+
+        matrix.insert_row(1, vec![7, 8, 9]);
+        matrix.insert_col(3, vec![0, 0, 0]);
+        assert_eq!(matrix.to_string(), "1 2 3 0\n7 8 9 0\n4 5 6 0\n");
+
+        assert_eq!(matrix.remove_col(1), [2, 8, 5]);
+        assert_eq!(matrix.remove_row(0), [1, 3, 0]);
+        assert_eq!(matrix.to_string(), "7 9 0\n4 6 0\n");
+
+        let mut empty: Matrix<u32> = Matrix::new(0, 0);
+        empty.insert_col(0, vec![1, 2]);
+        assert_eq!((empty.rows(), empty.cols()), (2, 1));
+
+        let mut tall: Matrix<u32> = Matrix::new(3, 0);
+        tall.insert_row(0, vec![1, 2]);
+        assert_eq!((tall.rows(), tall.cols()), (1, 2));
+        assert_eq!(tall.get(Pos(0, 1)), Some(&2));
+        let mut tall: Matrix<u32> = Matrix::new(3, 0);
+        tall.insert_row(3, vec![]);
+        assert_eq!((tall.rows(), tall.cols()), (4, 0));
+
+        let mut wide: Matrix<u32> = Matrix::new(0, 3);
+        wide.insert_col(0, vec![1, 2]);
+        assert_eq!((wide.rows(), wide.cols()), (2, 1));
+        assert_eq!(wide.to_string(), "1\n2\n");
+        let mut wide: Matrix<u32> = Matrix::new(0, 3);
+        wide.insert_row(0, vec![1, 2, 3]);
+        assert_eq!(wide.to_string(), "1 2 3\n");
+    }
+
+    #[test]
+    fn test_pad_concat_crop() {
+        let matrix = grid();
+
+        assert_eq!(matrix.pad(1, 0).to_string(), "0 0 0 0 0\n0 1 2 3 0\n0 4 5 6 0\n0 0 0 0 0\n");
+        assert_eq!(matrix.hconcat(&matrix).to_string(), "1 2 3 1 2 3\n4 5 6 4 5 6\n");
+        assert_eq!(matrix.vconcat(&matrix).rows(), 4);
+        assert_eq!(matrix.crop(Rect::new(Pos(0, 1), 2, 2)).unwrap().to_string(), "2 3\n5 6\n");
+        assert!(matrix.crop(Rect::new(Pos(1, 1), 2, 2)).is_none());
+    }
+
+    #[test]
+    fn test_tile() {
+        // Risk levels that grow by one per tile and wrap from 9 to 1
+        let matrix: Matrix<u32> = (vec![8, 9], 2).try_into().unwrap();
+
+        let tiled = matrix.tile(2, 2, |tile, val| (val + (tile.0 + tile.1) as u32 - 1) % 9 + 1);
+
+        assert_eq!(tiled.to_string(), "8 9 9 1\n9 1 1 2\n");
+    }
+}
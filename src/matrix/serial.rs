@@ -0,0 +1,84 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! `Matrix` serializes as its `vals`, `rows` and `cols`. Character grids can
+//! use [`text`] instead to be stored as puzzle text.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use super::{Error, Matrix};
+
+/// Unchecked form of `Matrix`, validated when converted.
+#[derive(Deserialize)]
+pub(super) struct RawMatrix<T> {
+    vals: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> TryFrom<RawMatrix<T>> for Matrix<T> {
+    type Error = Error;
+
+    fn try_from(value: RawMatrix<T>) -> Result<Self, Self::Error> {
+        if value.vals.len() != value.rows * value.cols {
+            return Err(Error::InvalidSize);
+        }
+
+        Ok(Self { vals: value.vals, rows: value.rows, cols: value.cols })
+    }
+}
+
+/// `Matrix<char>` as a single string with one line per row, for use with
+/// `#[serde(with = "...::matrix::serial::text")]`.
+pub mod text {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(matrix: &Matrix<char>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&matrix.to_puzzle_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Matrix<char>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.try_into().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::matrix::Pos;
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix: Matrix<u8> = (vec![1, 2, 3, 4, 5, 6], 3).try_into().unwrap();
+
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(json, r#"{"vals":[1,2,3,4,5,6],"rows":2,"cols":3}"#);
+
+        let back: Matrix<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.rows(), back.cols()), (2, 3));
+        assert!(back.iter().eq(matrix.iter()));
+
+        assert!(serde_json::from_str::<Matrix<u8>>(r#"{"vals":[1,2,3],"rows":2,"cols":3}"#).is_err());
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        #[derive(Serialize, Deserialize)]
+        struct Snapshot {
+            #[serde(with = "text")]
+            grid: Matrix<char>,
+            start: Pos,
+        }
+
+        let snapshot = Snapshot { grid: "S.#\n..#".try_into().unwrap(), start: Pos(0, 0) };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(json, r#"{"grid":"S.#\n..#\n","start":[0,0]}"#);
+
+        let back: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.grid.to_puzzle_string(), snapshot.grid.to_puzzle_string());
+        assert_eq!(back.start, snapshot.start);
+    }
+}
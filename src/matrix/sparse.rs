@@ -0,0 +1,262 @@
+use std::{cell::Cell, collections::{hash_map, HashMap}, fmt::Display, num::ParseIntError, ops::Index};
+
+use super::{Matrix, Pos, Rect};
+
+/// Grid storing only the cells that were set, for coordinates too spread
+/// out or unbounded for a dense [`Matrix`]. Tracks the corners of the set
+/// cells, only rescanning them after a cell on the edge is removed.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    bounds: Cell<Option<(Pos, Pos)>>,
+    stale: Cell<bool>,
+}
+
+fn grow(bounds: Option<(Pos, Pos)>, pos: Pos) -> (Pos, Pos) {
+    match bounds {
+        None => (pos, pos),
+        Some((min, max)) => (
+            Pos(min.0.min(pos.0), min.1.min(pos.1)),
+            Pos(max.0.max(pos.0), max.1.max(pos.1)),
+        ),
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self { cells: HashMap::new(), bounds: Cell::new(None), stale: Cell::new(false) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Pos, val: T) -> Option<T> {
+        if !self.stale.get() {
+            self.bounds.set(Some(grow(self.bounds.get(), pos)));
+        }
+        self.cells.insert(pos, val)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds.get()
+            && (pos.0 == min.0 || pos.0 == max.0 || pos.1 == min.1 || pos.1 == max.1)
+        {
+            self.stale.set(true);
+        }
+
+        Some(removed)
+    }
+
+    pub fn entry(&mut self, pos: Pos) -> hash_map::Entry<'_, Pos, T> {
+        // The entry may or may not be filled, so only grow on the next rescan
+        if !self.contains(pos) && self.bounds.get().is_none_or(|bounds| grow(Some(bounds), pos) != bounds) {
+            self.stale.set(true);
+        }
+        self.cells.entry(pos)
+    }
+
+    /// Smallest rect containing every set cell, `None` if there are none.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        if self.stale.get() {
+            self.bounds.set(self.cells.keys().fold(None, |bounds, pos| Some(grow(bounds, *pos))));
+            self.stale.set(false);
+        }
+
+        self.bounds.get().map(|(min, max)| Rect::from_corners(min, max))
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, Pos, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> hash_map::IterMut<'_, Pos, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn positions(&self) -> hash_map::Keys<'_, Pos, T> {
+        self.cells.keys()
+    }
+
+    /// Set cells at `pos + offset` for every offset, in the given order.
+    pub fn neighbors_with<'a>(&'a self, pos: Pos, offsets: &'a [Pos]) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let next = pos + *offset;
+            self.get(next).map(|val| (next, val))
+        })
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_with(pos, &Pos::NEIGHBORS4)
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_with(pos, &Pos::NEIGHBORS8)
+    }
+
+    /// Dense copy of the bounding box, with its origin moved to `(0,0)`.
+    /// Returns the offset that was subtracted from every position.
+    pub fn to_matrix(&self, empty: T) -> (Matrix<T>, Pos) where T: Clone + PartialEq {
+        let Some(rect) = self.bounding_box() else {
+            return (Matrix::with_default(0, 0, empty), Pos(0, 0));
+        };
+
+        let mut matrix = Matrix::with_default(rect.rows, rect.cols, empty);
+        for (pos, val) in self.iter() {
+            matrix[*pos - rect.origin] = val.clone();
+        }
+
+        (matrix, rect.origin)
+    }
+}
+
+impl SparseGrid<bool> {
+    /// Reads one `x,y` pair per line. `x` is the column and `y` the row.
+    pub fn from_points(input: &str) -> Result<Self, ParseIntError> {
+        let mut grid = Self::new();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (x, y) = line.split_once(',').unwrap_or((line, ""));
+            let col = x.trim().parse()?;
+            let row = y.trim().parse()?;
+            grid.insert(Pos(row, col), true);
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<T: Clone + PartialEq> Matrix<T> {
+    /// Keeps every cell that isn't `empty`.
+    pub fn to_sparse(&self, empty: &T) -> SparseGrid<T> {
+        self.iter_pos()
+            .filter(|(_, val)| *val != empty)
+            .map(|(pos, val)| (pos, val.clone()))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (pos, val) in iter {
+            grid.insert(pos, val);
+        }
+        grid
+    }
+}
+
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T> Index<Pos> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("Position should be set in the grid")
+    }
+}
+
+/// Draws the bounding box with `.` for unset cells.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(rect) = self.bounding_box() else { return Ok(()); };
+        let max = rect.max();
+
+        for row in rect.origin.0..=max.0 {
+            for col in rect.origin.1..=max.1 {
+                match self.get(Pos(row, col)) {
+                    Some(val) => write!(f, "{val}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_points() {
+        let grid = SparseGrid::from_points("7,1\n11,1\n11,7\n").unwrap();
+
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(Pos(1, 11)));
+        assert_eq!(grid.bounding_box(), Some(Rect::new(Pos(1, 7), 7, 5)));
+        assert_eq!(grid.neighbors8(Pos(0, 11)).count(), 1);
+        assert!(SparseGrid::from_points("1;2").is_err());
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix: Matrix<char> = "..#\n#..".try_into().unwrap();
+        let mut sparse = matrix.to_sparse(&'.');
+        sparse.insert(Pos(-1, 4), '@');
+
+        assert_eq!(sparse.to_string(), "....@\n..#..\n#....\n");
+
+        let (dense, offset) = sparse.to_matrix('.');
+        assert_eq!(offset, Pos(-1, 0));
+        assert_eq!((dense.rows(), dense.cols()), (3, 5));
+        assert_eq!(dense[Pos(0, 4)], '@');
+        assert_eq!(dense[Pos(1, 2)], '#');
+    }
+
+    #[test]
+    fn test_bounds_follow_cells() {
+        let mut grid: SparseGrid<char> = [(Pos(0, 0), '#'), (Pos(1, 1), '#')].into_iter().collect();
+
+        let _ = grid.entry(Pos(5, 5));
+        assert_eq!(grid.bounding_box(), Some(Rect::new(Pos(0, 0), 2, 2)));
+
+        grid.entry(Pos(-1, 0)).or_insert('@');
+        assert_eq!(grid.to_string(), "@.\n#.\n.#\n");
+
+        grid.remove(Pos(1, 1));
+        grid.remove(Pos(-1, 0));
+        assert_eq!(grid.to_string(), "#\n");
+        assert_eq!(grid.to_matrix('.').0.cols(), 1);
+
+        grid.remove(Pos(0, 0));
+        assert_eq!(grid.bounding_box(), None);
+
+        // Inner cells don't force a rescan
+        let mut grid: SparseGrid<char> = [(Pos(0, 0), '#'), (Pos(1, 1), '#'), (Pos(2, 2), '#')].into_iter().collect();
+        grid.remove(Pos(1, 1));
+        grid.entry(Pos(1, 2)).or_insert('@');
+        assert!(!grid.stale.get());
+        assert_eq!(grid.bounding_box(), Some(Rect::new(Pos(0, 0), 3, 3)));
+    }
+}
@@ -0,0 +1,259 @@
+use std::{fmt::Display, ops::{Index, IndexMut}};
+
+use super::{DisplayOptions, Matrix, Pos, PosIter};
+
+/// Axis-aligned block of `rows` x `cols` cells with its top-left corner at
+/// `origin`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub origin: Pos,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Rect {
+    pub fn new(origin: Pos, rows: usize, cols: usize) -> Self {
+        Self { origin, rows, cols }
+    }
+
+    /// Smallest rect containing both corners.
+    pub fn from_corners(a: Pos, b: Pos) -> Self {
+        let origin = Pos(a.0.min(b.0), a.1.min(b.1));
+        let rows = a.0.abs_diff(b.0) as usize + 1;
+        let cols = a.1.abs_diff(b.1) as usize + 1;
+        Self { origin, rows, cols }
+    }
+
+    /// Bottom-right cell, inclusive.
+    pub fn max(&self) -> Pos {
+        self.origin + Pos(self.rows as i32 - 1, self.cols as i32 - 1)
+    }
+
+    pub fn area(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        let local = pos - self.origin;
+        local.0 >= 0 && local.1 >= 0 && (local.0 as usize) < self.rows && (local.1 as usize) < self.cols
+    }
+
+    /// Positions inside the rect, in row-major order.
+    pub fn iter_pos(&self) -> impl Iterator<Item = Pos> + use<> {
+        let origin = self.origin;
+        Pos::iter(self.rows, self.cols).map(move |pos| pos + origin)
+    }
+
+    fn fits(&self, rows: usize, cols: usize) -> bool {
+        self.origin.0 >= 0
+            && self.origin.1 >= 0
+            && self.origin.0 as usize + self.rows <= rows
+            && self.origin.1 as usize + self.cols <= cols
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Borrowed window over `rect`, or `None` if it doesn't fit inside.
+    pub fn view(&self, rect: Rect) -> Option<MatrixView<'_, T>> {
+        rect.fits(self.rows, self.cols).then_some(MatrixView { matrix: self, rect })
+    }
+
+    pub fn view_mut(&mut self, rect: Rect) -> Option<MatrixViewMut<'_, T>> {
+        rect.fits(self.rows, self.cols).then_some(MatrixViewMut { matrix: self, rect })
+    }
+}
+
+/// Read-only window into a [`Matrix`]. Positions are local to the window.
+#[derive(Debug)]
+pub struct MatrixView<'a, T> {
+    matrix: &'a Matrix<T>,
+    rect: Rect,
+}
+
+// Manual impls: deriving would needlessly require `T: Clone`
+impl<T> Clone for MatrixView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MatrixView<'_, T> {}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rect.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.rect.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&'a T> {
+        let global = pos + self.rect.origin;
+        self.rect.contains(global).then(|| &self.matrix[global])
+    }
+
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.rect.rows, "Row {row} out of bounds for {} rows", self.rect.rows);
+        let start = self.rect.origin.1 as usize;
+        &self.matrix.row(self.rect.origin.0 as usize + row)[start..start + self.rect.cols]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let view = *self;
+        (0..self.rect.rows).flat_map(move |row| view.row(row).iter())
+    }
+
+    pub fn iter_pos(&self) -> std::iter::Zip<PosIter, impl Iterator<Item = &'a T> + use<'a, T>> {
+        Pos::iter(self.rect.rows, self.rect.cols).zip(self.iter())
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> where T: Clone {
+        Matrix { vals: self.iter().cloned().collect(), rows: self.rect.rows, cols: self.rect.cols }
+    }
+}
+
+impl<T> Index<Pos> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("Position should be inside the view")
+    }
+}
+
+impl<T: Display> Display for MatrixView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display_with(DisplayOptions::default()).fmt(f)
+    }
+}
+
+/// Mutable window into a [`Matrix`]. Positions are local to the window.
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    matrix: &'a mut Matrix<T>,
+    rect: Rect,
+}
+
+impl<T> MatrixViewMut<'_, T> {
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView { matrix: self.matrix, rect: self.rect }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rect.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.rect.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.as_view().get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        let global = pos + self.rect.origin;
+        if self.rect.contains(global) { Some(&mut self.matrix[global]) } else { None }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        self.as_view().row(row)
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.rect.rows, "Row {row} out of bounds for {} rows", self.rect.rows);
+        let start = self.rect.origin.1 as usize;
+        &mut self.matrix.row_mut(self.rect.origin.0 as usize + row)[start..start + self.rect.cols]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.as_view().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let Rect { origin, rows, cols } = self.rect;
+        let start = origin.1 as usize;
+        self.matrix.rows_iter_mut()
+            .skip(origin.0 as usize)
+            .take(rows)
+            .flat_map(move |row| row[start..start + cols].iter_mut())
+    }
+
+    pub fn iter_pos(&self) -> impl Iterator<Item = (Pos, &T)> {
+        Pos::iter(self.rect.rows, self.rect.cols).zip(self.iter())
+    }
+
+    pub fn iter_mut_pos(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        Pos::iter(self.rect.rows, self.rect.cols).zip(self.iter_mut())
+    }
+
+    pub fn fill(&mut self, val: T) where T: Clone {
+        self.iter_mut().for_each(|cell| *cell = val.clone());
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> where T: Clone {
+        self.as_view().to_matrix()
+    }
+}
+
+impl<T> Index<Pos> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("Position should be inside the view")
+    }
+}
+
+impl<T> IndexMut<Pos> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, index: Pos) -> &mut Self::Output {
+        self.get_mut(index).expect("Position should be inside the view")
+    }
+}
+
+impl<T: Display> Display for MatrixViewMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let matrix: Matrix<char> = "abcd\nefgh\nijkl".try_into().unwrap();
+        let view = matrix.view(Rect::new(Pos(1, 1), 2, 2)).unwrap();
+
+        assert_eq!(view[Pos(0, 0)], 'f');
+        assert_eq!(view.get(Pos(1, 1)), Some(&'k'));
+        assert_eq!(view.get(Pos(2, 0)), None);
+        assert_eq!(view.iter().collect::<String>(), "fgjk");
+        assert_eq!(view.iter_pos().last(), Some((Pos(1, 1), &'k')));
+        assert_eq!(view.to_string(), "f g\nj k\n");
+        assert!(view.to_matrix().iter().eq(view.iter()));
+
+        assert!(matrix.view(Rect::new(Pos(2, 2), 2, 2)).is_none());
+        assert!(matrix.view(Rect::new(Pos(-1, 0), 1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_view_mut() {
+        let mut matrix: Matrix<char> = "....\n....\n....".try_into().unwrap();
+        let mut view = matrix.view_mut(Rect::from_corners(Pos(2, 3), Pos(1, 2))).unwrap();
+
+        view.fill('#');
+        view[Pos(0, 0)] = '@';
+
+        let expected: Matrix<char> = "....\n..@#\n..##".try_into().unwrap();
+        assert!(matrix.iter().eq(expected.iter()));
+    }
+}
@@ -0,0 +1,69 @@
+use super::{Matrix, Pos};
+
+/// Toroidal access: positions outside the matrix wrap around modulo its
+/// rows and columns, so every `Pos` is valid on a non-empty matrix.
+impl<T> Matrix<T> {
+    pub fn wrap_pos(&self, pos: Pos) -> Pos {
+        assert!(self.rows != 0 && self.cols != 0, "Can't wrap positions on an empty matrix");
+        Pos(pos.0.rem_euclid(self.rows as i32), pos.1.rem_euclid(self.cols as i32))
+    }
+
+    pub fn get_wrapped(&self, pos: Pos) -> &T {
+        &self[self.wrap_pos(pos)]
+    }
+
+    pub fn get_wrapped_mut(&mut self, pos: Pos) -> &mut T {
+        let pos = self.wrap_pos(pos);
+        &mut self[pos]
+    }
+
+    /// Cells at `pos + offset` for every offset, wrapped onto the matrix.
+    pub fn neighbors_wrapped_with<'a>(&'a self, pos: Pos, offsets: &'a [Pos]) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        offsets.iter().map(move |offset| {
+            let next = self.wrap_pos(pos + *offset);
+            (next, &self[next])
+        })
+    }
+
+    pub fn neighbors4_wrapped(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_wrapped_with(pos, &Pos::NEIGHBORS4)
+    }
+
+    pub fn neighbors8_wrapped(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors_wrapped_with(pos, &Pos::NEIGHBORS8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search;
+
+    #[test]
+    fn test_wrapped_access() {
+        let mut matrix: Matrix<char> = "abc\ndef".try_into().unwrap();
+
+        assert_eq!(matrix.wrap_pos(Pos(-1, 7)), Pos(1, 1));
+        assert_eq!(*matrix.get_wrapped(Pos(2, -1)), 'c');
+
+        *matrix.get_wrapped_mut(Pos(-2, -3)) = 'z';
+        assert_eq!(matrix[Pos(0, 0)], 'z');
+
+        let corner: Vec<Pos> = matrix.neighbors4_wrapped(Pos(0, 0)).map(|(pos, _)| pos).collect();
+        assert_eq!(corner, [Pos(1, 0), Pos(0, 1), Pos(1, 0), Pos(0, 2)]);
+    }
+
+    #[test]
+    fn test_search_on_torus() {
+        let matrix: Matrix<char> = ".#...\n.#...\n.#...".try_into().unwrap();
+
+        let search = search::bfs(
+            [Pos(0, 0)],
+            |pos| matrix.neighbors4_wrapped(*pos).filter(|(_, c)| **c != '#').map(|(pos, _)| pos).collect::<Vec<_>>(),
+            |pos| *pos == Pos(0, 2),
+        );
+
+        // Through the left edge instead of around the wall
+        assert_eq!(search.goal_cost(), Some(3));
+    }
+}
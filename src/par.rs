@@ -0,0 +1,48 @@
+//! Helpers for days where every input line is an independent item. They run
+//! on rayon's thread pool with the `rayon` feature and sequentially without
+//! it, so answers can call them either way.
+
+use std::iter::Sum;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// `op` applied to every item, keeping their order.
+pub fn map_items<T: Sync, R: Send>(items: &[T], op: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    let items = items.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let items = items.iter();
+
+    items.map(op).collect()
+}
+
+/// Sum of `op` over every item.
+pub fn sum_by<T: Sync, R: Send + Sum>(items: &[T], op: impl Fn(&T) -> R + Sync + Send) -> R {
+    #[cfg(feature = "rayon")]
+    let items = items.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let items = items.iter();
+
+    items.map(op).sum()
+}
+
+/// `op` applied to every line of `contents`, keeping their order.
+pub fn map_lines<R: Send>(contents: &str, op: impl Fn(&str) -> R + Sync + Send) -> Vec<R> {
+    let lines: Vec<&str> = contents.lines().collect();
+    map_items(&lines, |line| op(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helpers() {
+        let lengths = map_lines("a\nbcd\n\nef", str::len);
+
+        assert_eq!(lengths, [1, 3, 0, 2]);
+        assert_eq!(sum_by(&lengths, |len| len * 2), 12);
+        assert_eq!(map_items(&lengths, |len| len + 1), [2, 4, 1, 3]);
+    }
+}
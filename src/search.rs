@@ -0,0 +1,354 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash, ops::Add};
+
+/// Everything a search learned about the states it settled: the cheapest
+/// cost to each, how many cheapest paths reach it and through which
+/// predecessors. States still waiting in the frontier when the search
+/// stopped at a goal are treated as unreached.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    pub goal: Option<S>,
+    ids: HashMap<S, usize>,
+    states: Vec<S>,
+    costs: Vec<C>,
+    counts: Vec<u64>,
+    parents: Vec<Vec<usize>>,
+    settled: Vec<bool>,
+}
+
+impl<S: Hash + Eq + Clone, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Self {
+            goal: None,
+            ids: HashMap::new(),
+            states: Vec::new(),
+            costs: Vec::new(),
+            counts: Vec::new(),
+            parents: Vec::new(),
+            settled: Vec::new(),
+        }
+    }
+
+    fn id(&mut self, state: &S, cost: C) -> (usize, bool) {
+        if let Some(&id) = self.ids.get(state) {
+            return (id, false);
+        }
+
+        let id = self.states.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state.clone());
+        self.costs.push(cost);
+        self.counts.push(0);
+        self.parents.push(Vec::new());
+        self.settled.push(false);
+        (id, true)
+    }
+
+    fn settled_id(&self, state: &S) -> Option<usize> {
+        self.ids.get(state).copied().filter(|&id| self.settled[id])
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.settled_id(state).map(|id| self.costs[id])
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    /// Number of distinct cheapest paths from any source to `state`.
+    pub fn path_count(&self, state: &S) -> u64 {
+        self.settled_id(state).map_or(0, |id| self.counts[id])
+    }
+
+    /// Every state that reaches `state` along some cheapest path.
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.settled_id(state)
+            .into_iter()
+            .flat_map(|id| self.parents[id].iter().map(|&parent| &self.states[parent]))
+    }
+
+    /// One cheapest path from a source to `state`, including both ends.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        let mut id = self.settled_id(state)?;
+        let mut path = vec![self.states[id].clone()];
+        while let Some(&parent) = self.parents[id].first() {
+            path.push(self.states[parent].clone());
+            id = parent;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// All states lying on at least one cheapest path to `state`.
+    pub fn optimal_states(&self, state: &S) -> HashSet<S> {
+        let mut res = HashSet::new();
+        let mut stack: Vec<usize> = self.settled_id(state).into_iter().collect();
+        let mut seen = vec![false; self.states.len()];
+        while let Some(id) = stack.pop() {
+            if seen[id] { continue; }
+            seen[id] = true;
+
+            res.insert(self.states[id].clone());
+            stack.extend(&self.parents[id]);
+        }
+
+        res
+    }
+
+    /// Every settled state with its cheapest cost.
+    pub fn costs(&self) -> impl Iterator<Item = (&S, C)> {
+        self.states.iter()
+            .zip(self.costs.iter().copied())
+            .zip(&self.settled)
+            .filter(|(_, settled)| **settled)
+            .map(|(state, _)| state)
+    }
+}
+
+/// Breadth-first search where every step costs 1. Stops at the first state
+/// matching `is_goal`, or explores everything reachable if none does.
+pub fn bfs<S, I>(
+    sources: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        let (id, new) = search.id(&source, 0);
+        if new {
+            search.counts[id] = 1;
+            queue.push_back(id);
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        search.settled[id] = true;
+        let state = search.states[id].clone();
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        let next_cost = search.costs[id] + 1;
+        for next in neighbors(&state) {
+            let (next_id, new) = search.id(&next, next_cost);
+            if new {
+                queue.push_back(next_id);
+            } else if search.costs[next_id] != next_cost {
+                continue;
+            }
+
+            search.counts[next_id] += search.counts[id];
+            search.parents[next_id].push(id);
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm over non-negative step costs.
+pub fn dijkstra<S, C, I>(
+    sources: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Ord + Add<Output = C> + Default + Copy,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(sources, neighbors, |_| C::default(), is_goal)
+}
+
+/// A* search. Costs are exact as long as `heuristic` never overestimates,
+/// as a state reached again more cheaply is reopened. Path counts and
+/// predecessors also need it to be consistent (never decrease by more than
+/// the step cost).
+pub fn astar<S, C, I>(
+    sources: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Ord + Add<Output = C> + Default + Copy,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for source in sources {
+        let (id, new) = search.id(&source, C::default());
+        if new {
+            search.counts[id] = 1;
+            heap.push(Reverse((heuristic(&source), C::default(), id)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, id))) = heap.pop() {
+        if search.settled[id] || cost > search.costs[id] { continue; }
+        search.settled[id] = true;
+
+        let state = search.states[id].clone();
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            let (next_id, new) = search.id(&next, next_cost);
+            if new {
+                // First route found, queued below
+            } else if next_cost > search.costs[next_id] || (next_cost == search.costs[next_id] && search.settled[next_id]) {
+                continue;
+            } else if next_cost < search.costs[next_id] {
+                // Only possible for a settled state if the heuristic is inconsistent
+                search.costs[next_id] = next_cost;
+                search.counts[next_id] = 0;
+                search.parents[next_id].clear();
+                search.settled[next_id] = false;
+            } else {
+                // Equal cost: another cheapest route, already queued
+                search.counts[next_id] += search.counts[id];
+                search.parents[next_id].push(id);
+                continue;
+            }
+
+            search.counts[next_id] += search.counts[id];
+            search.parents[next_id].push(id);
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, next_id)));
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs_counts_shortest_paths() {
+        // 3x3 open grid: C(4,2) shortest paths corner to corner
+        let neighbors = |&(r, c): &(i32, i32)| {
+            [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
+                .into_iter()
+                .filter(|(r, c)| (0..3).contains(r) && (0..3).contains(c))
+        };
+
+        let search = bfs([(0, 0)], neighbors, |state| *state == (2, 2));
+
+        assert_eq!(search.goal, Some((2, 2)));
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.path_count(&(2, 2)), 6);
+        assert_eq!(search.predecessors(&(2, 2)).count(), 2);
+        assert_eq!(search.path(&(2, 2)).unwrap().len(), 5);
+        assert_eq!(search.optimal_states(&(2, 2)).len(), 9);
+    }
+
+    #[test]
+    fn test_early_stop_hides_frontier() {
+        let neighbors = |&(r, c): &(i32, i32)| {
+            [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
+                .into_iter()
+                .filter(|(r, c)| (0..3).contains(r) && (0..3).contains(c))
+        };
+
+        // (1, 1) is queued through (1, 0) only when the goal is reached
+        let search = bfs([(0, 0)], neighbors, |state| *state == (0, 1));
+        assert_eq!(search.goal_cost(), Some(1));
+        assert_eq!(search.cost(&(1, 0)), Some(1));
+        assert_eq!(search.cost(&(1, 1)), None);
+        assert_eq!(search.path_count(&(1, 1)), 0);
+        assert!(search.path(&(1, 1)).is_none());
+        assert!(search.costs().all(|(state, _)| *state != (1, 1)));
+
+        // "c" is first queued at cost 4, later lowered to 3 through "b"
+        let edges: HashMap<&str, Vec<(&str, u32)>> = HashMap::from([
+            ("a", vec![("c", 4), ("b", 1), ("d", 1)]),
+            ("b", vec![("c", 2)]),
+            ("c", vec![]),
+            ("d", vec![]),
+        ]);
+        let search = dijkstra(["a"], |state| edges[state].clone(), |state| *state == "d");
+        assert_eq!(search.goal_cost(), Some(1));
+        assert_eq!(search.cost(&"c"), None);
+    }
+
+    #[test]
+    fn test_bfs_multiple_sources() {
+        let neighbors = |&n: &i32| [n - 1, n + 1].into_iter().filter(|n| (0..=10).contains(n));
+
+        let search = bfs([0, 10], neighbors, |_| false);
+
+        assert_eq!(search.cost(&5), Some(5));
+        assert_eq!(search.path_count(&5), 2);
+        assert_eq!(search.cost(&7), Some(3));
+        assert_eq!(search.path_count(&7), 1);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let edges: HashMap<&str, Vec<(&str, u32)>> = HashMap::from([
+            ("a", vec![("b", 1), ("c", 4)]),
+            ("b", vec![("c", 2), ("d", 5)]),
+            ("c", vec![("d", 1)]),
+            ("d", vec![]),
+        ]);
+        let neighbors = |state: &&str| edges[state].clone();
+
+        let search = dijkstra(["a"], neighbors, |state| *state == "d");
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.path(&"d").unwrap(), ["a", "b", "c", "d"]);
+        assert_eq!(search.path_count(&"d"), 1);
+
+        let heuristic = |state: &&str| match *state { "a" => 3, "b" => 2, "c" => 1, _ => 0 };
+        let search = astar(["a"], neighbors, heuristic, |state| *state == "d");
+        assert_eq!(search.goal_cost(), Some(4));
+        assert_eq!(search.path_count(&"d"), 1);
+    }
+
+    #[test]
+    fn test_astar_reopens_with_inconsistent_heuristic() {
+        // "c" is settled through "b" first, "a" only later finds it cheaper
+        let edges: HashMap<&str, Vec<(&str, u32)>> = HashMap::from([
+            ("s", vec![("a", 1), ("b", 1)]),
+            ("a", vec![("c", 1)]),
+            ("b", vec![("c", 3)]),
+            ("c", vec![("g", 10)]),
+            ("g", vec![]),
+        ]);
+        let heuristic = |state: &&str| if *state == "a" { 11 } else { 0 };
+
+        let search = astar(["s"], |state| edges[state].clone(), heuristic, |state| *state == "g");
+
+        assert_eq!(search.goal_cost(), Some(12));
+        assert_eq!(search.path(&"g").unwrap(), ["s", "a", "c", "g"]);
+        assert_eq!(search.path_count(&"g"), 1);
+    }
+
+    #[test]
+    fn test_dijkstra_all_predecessors() {
+        let edges: HashMap<u8, Vec<(u8, u32)>> = HashMap::from([
+            (0, vec![(1, 1), (2, 2)]),
+            (1, vec![(3, 2)]),
+            (2, vec![(3, 1)]),
+            (3, vec![]),
+        ]);
+
+        let search = dijkstra([0], |state| edges[state].clone(), |_| false);
+
+        let mut preds: Vec<u8> = search.predecessors(&3).copied().collect();
+        preds.sort();
+        assert_eq!(preds, [1, 2]);
+        assert_eq!(search.path_count(&3), 2);
+        assert_eq!(search.optimal_states(&3), HashSet::from([0, 1, 2, 3]));
+    }
+}